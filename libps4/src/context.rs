@@ -0,0 +1,88 @@
+/**************************************************************************/
+/* context.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+thread_local! {
+    /// Contexts entered on this thread, innermost last
+    static CURRENT: RefCell<Vec<Context>> = const { RefCell::new(vec![]) };
+}
+
+/// Where libps4 works and how it talks to the user, given to every command and query.
+///
+/// Nothing is read from or written to the environment, two contexts can be used one after another
+/// or on different threads without one leaking into the other.
+#[derive(Clone, Default, Debug)]
+pub struct Context {
+    /// Root of the system to operate on, None for /
+    pub root: Option<PathBuf>,
+    /// Config file, None for /etc/ps4.d/ps4.conf inside the root
+    pub config: Option<PathBuf>,
+    /// Package cache, None for /var/cache/ps4 inside the root
+    pub cache_dir: Option<PathBuf>,
+    /// Answer prompts without asking, with the answers below or else the safe default of each prompt
+    pub noconfirm: bool,
+    /// Colour messages or not, None colours a terminal when `colour` is on in the config
+    pub color: Option<bool>,
+    /// Print more about what ps4 is doing, e.g. every request
+    pub verbose: bool,
+    /// Answer to downgrade prompts, true allows the downgrade
+    pub downgrade: Option<bool>,
+    /// Answer to file conflict prompts, true overwrites the files
+    pub file_conflicts: Option<bool>,
+    /// Answer to prompts replacing an installed provider, true accepts the replacement
+    pub replace: Option<bool>,
+    /// Answer to prompts breaking a lock its owner didn't release, true breaks it
+    pub stale_lock: Option<bool>
+}
+
+/// Keeps a context entered on the current thread, the previous one is back once it's dropped.
+pub struct ContextGuard {
+    /// Entered contexts belong to the thread, so is the guard
+    _not_send: PhantomData<*const ()>
+}
+
+impl Context {
+    /// Makes this the context of everything the current thread does until the guard is dropped.
+    ///
+    /// Every call enters the context it's given, this is only needed to print with [crate::ps4output] outside of one.
+    pub fn enter(&self) -> ContextGuard {
+        CURRENT.with(|c| c.borrow_mut().push(self.clone()));
+
+        ContextGuard { _not_send: PhantomData }
+    }
+
+    /// Returns the context entered on the current thread, the default one outside of any call
+    pub(crate) fn current() -> Context {
+        CURRENT.with(|c| c.borrow().last().cloned().unwrap_or_default())
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.borrow_mut().pop());
+    }
+}
@@ -0,0 +1,233 @@
+/**************************************************************************/
+/* ps4db.rs                                                                */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+pub struct _PS4pkgstructer {
+    pub name: String,
+    pub version: String,
+    pub upstream: i32,
+    pub description: String,
+    pub groups: Vec<String>,
+    pub url: String,
+    pub license: Vec<String>,
+    pub depends: Vec<String>,
+    pub optional_depends: Vec<String>,
+    pub provides: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub replaces: Vec<String>,
+    pub sha512sum: String
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct InstalledPS4Packages {
+    pub name: String,
+    pub groups: Vec<String>,
+    pub source: String,
+    pub version: String,
+    pub upstream: i32,
+    pub provides: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub dependencies: Vec<String>
+}
+
+/// The kind of file system entry a package installed.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum FileType {
+    File,
+    Dir,
+    Symlink,
+    Hardlink
+}
+
+impl FileType {
+    /// Returns the name stored in the files table
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileType::File => "file",
+            FileType::Dir => "dir",
+            FileType::Symlink => "symlink",
+            FileType::Hardlink => "hardlink",
+        }
+    }
+
+    /// Parses a name from the files table, anything unknown reads as a file
+    pub fn from_name(name: &str) -> FileType {
+        match name {
+            "dir" => FileType::Dir,
+            "symlink" => FileType::Symlink,
+            "hardlink" => FileType::Hardlink,
+            _ => FileType::File
+        }
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// What a file looked like when it was installed, compared against by `ps4 verify`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct FileMetadata {
+    /// Permission bits, including setuid, setgid and sticky
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    /// Seconds since the epoch
+    pub mtime: i64,
+    /// Lowercase hex sha256 of the content, of the link target for symlinks, None for directories
+    pub sha256: Option<String>
+}
+
+/// A file system entry owned by an installed package, the path is absolute inside the root.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct InstalledFile {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub file_type: FileType,
+    /// None for entries recorded before ps4 kept metadata, only their presence can be verified
+    pub metadata: Option<FileMetadata>
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct Source {
+    pub name: String,
+    pub url: Option<String>
+}
+
+/// What a transaction did to a single package.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryAction {
+    Install,
+    Remove,
+    Upgrade,
+    Downgrade,
+    Reinstall
+}
+
+impl fmt::Display for HistoryAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryAction::Install => f.pad("install"),
+            HistoryAction::Remove => f.pad("remove"),
+            HistoryAction::Upgrade => f.pad("upgrade"),
+            HistoryAction::Downgrade => f.pad("downgrade"),
+            HistoryAction::Reinstall => f.pad("reinstall"),
+        }
+    }
+}
+
+/// A single package change within a transaction, versions are stored as "version-upstream".
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HistoryChange {
+    pub name: String,
+    pub action: HistoryAction,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    /// Source column of the package before the change, used to restore it on undo
+    pub old_source: Option<String>
+}
+
+/// A row of the history table.
+#[derive(Clone)]
+pub struct HistoryTransaction {
+    pub id: i64,
+    /// Seconds since the unix epoch
+    pub timestamp: i64,
+    pub command: String,
+    pub changes: Vec<HistoryChange>
+}
+
+
+/// A row of the holds table, a held package is never changed unless its new version matches the glob.
+#[derive(Clone)]
+pub struct Hold {
+    pub name: String,
+    /// Version glob such as `13.*`, None holds the package at whatever is installed
+    pub version: Option<String>
+}
+
+impl Hold {
+    /// Returns true if the package may be changed to this version
+    pub fn allows(&self, version: &str) -> bool {
+        let glob = match &self.version {
+            Some(glob) => glob,
+            None => return false
+        };
+
+        let pattern = format!("^{}$", regex::escape(glob).replace("\\*", ".*").replace("\\?", "."));
+
+        regex::Regex::new(&pattern).map(|r| r.is_match(version)).unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Hold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{} {}", self.name, version),
+            None => write!(f, "{}", self.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hold(version: Option<&str>) -> Hold {
+        Hold { name: "gcc".to_string(), version: version.map(|v| v.to_string()) }
+    }
+
+    #[test]
+    fn hold_without_a_version_allows_nothing() {
+        assert!(!hold(None).allows("13.2"));
+        assert!(!hold(None).allows(""));
+    }
+
+    #[test]
+    fn hold_matches_the_whole_version() {
+        let held = hold(Some("13.*"));
+        assert!(held.allows("13.2"));
+        assert!(held.allows("13.2.1"));
+        assert!(!held.allows("14.1"));
+        assert!(!held.allows("113.2"));
+        assert!(!held.allows("13"));
+    }
+
+    #[test]
+    fn hold_globs_match_single_characters_and_literals() {
+        assert!(hold(Some("13.?")).allows("13.2"));
+        assert!(!hold(Some("13.?")).allows("13.21"));
+        assert!(hold(Some("13.2")).allows("13.2"));
+        // Dots and other regex characters in the glob are literal
+        assert!(!hold(Some("13.2")).allows("1302"));
+        assert!(hold(Some("1.0+git*")).allows("1.0+git20240101"));
+    }
+}
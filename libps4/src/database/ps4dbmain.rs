@@ -0,0 +1,599 @@
+/**************************************************************************/
+/* ps4db.rs                                                               */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+#![allow(clippy::all)]
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use crate::{database::ps4db::Source, ps4_package_progess_bar::{string_to_vec, vec_to_string}, packaging::ps4_packageing_setup::{PS4NewPackage, PS4Package}};
+use std::{collections::HashSet, time::{SystemTime, UNIX_EPOCH}, vec};
+use crate::ps4_package_config::ps4_mirror_config_main::{get_enabled_repos, get_repo_config};
+use crate::ps4_package_progess_bar::db_path;
+use crate::ps4_package_transactions::atom::{parse_atoms, PackageAtom};
+use crate::ps4error::Ps4Error;
+
+use super::ps4db::{FileMetadata, FileType, HistoryChange, HistoryTransaction, Hold, InstalledFile, InstalledPS4Packages};
+use crate::ps4output::warning;
+
+/// Reads the installed_files column of packages installed before the files table existed.
+///
+/// It held the paths joined with commas, those read as plain files without metadata,
+/// so only the presence of these files can be verified.
+fn files_from_string(files: &str) -> Vec<InstalledFile> {
+    files.split(",")
+        .filter(|path| !path.is_empty())
+        .map(|path| InstalledFile { path: path.to_string(), file_type: FileType::File, metadata: None })
+        .collect()
+}
+
+/// Reads a row of the files table
+fn file_from_row(file: &Row) -> Result<InstalledFile, rusqlite::Error> {
+    // Every metadata column is set together, mode alone tells whether the entry has any
+    let metadata = match file.get::<usize, Option<u32>>(2)? {
+        Some(mode) => Some(FileMetadata {
+            mode,
+            uid: file.get(3)?,
+            gid: file.get(4)?,
+            size: file.get::<usize, i64>(5)? as u64,
+            mtime: file.get(6)?,
+            sha256: file.get(7)?
+        }),
+        None => None
+    };
+
+    Ok(InstalledFile {
+        path: file.get(0)?,
+        file_type: FileType::from_name(&file.get::<usize, String>(1)?),
+        metadata
+    })
+}
+
+/// Reads a row of the installed_packages table
+fn installed_from_row(package: &Row) -> Result<InstalledPS4Packages, rusqlite::Error> {
+    Ok(InstalledPS4Packages {
+        name: package.get(0)?,
+        groups: string_to_vec(package.get::<usize, String>(1)?),
+        source: package.get(2)?,
+        version: package.get(3)?,
+        upstream: package.get(4)?,
+        provides: string_to_vec(package.get::<usize, String>(6)?),
+        conflicts: string_to_vec(package.get::<usize, String>(7)?),
+        dependencies: string_to_vec(package.get::<usize, String>(8)?)
+    })
+}
+
+/// Reads a row of a repo's packages table
+fn remote_from_row(package: &Row) -> Result<PS4Package, rusqlite::Error> {
+    Ok(PS4Package {
+        name: package.get(0)?,
+        version: package.get(1)?,
+        upstream: package.get(2)?,
+        description: package.get(3)?,
+        groups: package.get(4)?,
+        url: package.get(5)?,
+        license: package.get(6)?,
+        depends: package.get(7)?,
+        optional_depends: package.get(8)?,
+        provides: package.get(9)?,
+        conflicts: package.get(10)?,
+        replaces: package.get(11)?,
+        sha512sum: package.get(12)?
+    })
+}
+
+/// Opens a repo database, None if the repo hasn't been synced yet
+fn open_repo(repo: &String) -> Result<Option<Connection>, Ps4Error> {
+    if !db_path(repo).exists() {
+        return Ok(None);
+    }
+
+    let conn = Connection::open(db_path(repo))?;
+
+    let synced: i64 = conn.query_row("SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = 'packages'", [], |r| r.get(0))?;
+
+    if synced == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(conn))
+}
+
+/// Creates a database containing locally installed packages and various information
+pub fn init_database() -> Result<(), Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    conn.execute(
+        "create table if not exists installed_packages
+            (
+                name text not null unique primary key,
+                groups text,
+                source text not null,
+                version text not null,
+                upstream integer not null,
+                installed_files text,
+                provides text,
+                conflicts text,
+                dependencies text
+            )",
+        [],
+    )?;
+
+    conn.execute(
+        "create table if not exists repos
+            (
+                name text not null unique primary key,
+                repo_hash text not null,
+                last_updated text not null
+            )",
+        [],
+    )?;
+
+    create_history_table(&conn)?;
+    create_holds_table(&conn)?;
+    create_files_table(&conn)?;
+
+    add_package_to_installed(PS4NewPackage {
+        name: "ps4".to_string(),
+        groups: "core".to_string(),
+        version: crate::get_version().to_string(),
+        upstream: 0,
+        installed_files: vec![],
+        provides: vec!["ps4".to_string()],
+        conflicts: vec![],
+        dependencies: vec!["curl".to_string(), "sqlite".to_string()],
+    }, Source{
+        name: "core".to_string(),
+        url: None
+    })
+}
+
+/// Adds a package to the installed packages database
+pub fn add_package_to_installed(package: PS4NewPackage, source: Source) -> Result<(), Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_files_table(&conn)?;
+
+    // Convert source into a string
+    let package_source: String;
+    if source.url.is_none() {
+        package_source = source.name
+    } else {
+        package_source = format!("{},{}", source.name, source.url.unwrap());
+    }
+
+    // The package and its files are replaced together, a reinstall never keeps the old version's entries
+    let transaction = conn.unchecked_transaction()?;
+
+    // The installed_files column is only read for packages installed before the files table existed
+    transaction.execute("
+        INSERT OR REPLACE INTO installed_packages (name, groups, source, version, upstream, installed_files, provides, conflicts, dependencies)
+        VALUES (?1, ?2, ?3, ?4, ?5, '', ?6, ?7, ?8);",
+        params![package.name,
+        package.groups,
+        package_source,
+        package.version,
+        package.upstream,
+        vec_to_string(package.provides),
+        vec_to_string(package.conflicts),
+        vec_to_string(package.dependencies)]
+    )?;
+
+    transaction.execute("DELETE FROM files WHERE package = ?", [&package.name])?;
+
+    {
+        let mut statement = transaction.prepare("
+            INSERT OR REPLACE INTO files (package, path, type, mode, uid, gid, size, mtime, sha256)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);")?;
+
+        for file in &package.installed_files {
+            let metadata = file.metadata.as_ref();
+
+            statement.execute(params![package.name,
+                file.path,
+                file.file_type.as_str(),
+                metadata.map(|m| m.mode),
+                metadata.map(|m| m.uid),
+                metadata.map(|m| m.gid),
+                metadata.map(|m| m.size as i64),
+                metadata.map(|m| m.mtime),
+                metadata.and_then(|m| m.sha256.clone())]
+            )?;
+        }
+    }
+
+    transaction.commit()?;
+
+    Ok(())
+}
+
+/// Returns files owned by a package, sorted by path
+pub fn return_owned_files(package: &String) -> Result<Vec<InstalledFile>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_files_table(&conn)?;
+
+    let mut statement = conn.prepare("SELECT path, type, mode, uid, gid, size, mtime, sha256 FROM files WHERE package = ? ORDER BY path")?;
+
+    let files = statement.query_map([package], file_from_row)?
+        .collect::<Result<Vec<InstalledFile>, rusqlite::Error>>()?;
+
+    if !files.is_empty() {
+        return Ok(files);
+    }
+
+    // Installed before the files table existed
+    let legacy: Option<Option<String>> = conn.query_row("SELECT installed_files FROM installed_packages WHERE name = ?", [package], |r| r.get(0))
+        .optional()?;
+
+    let mut files = files_from_string(&legacy.flatten().unwrap_or_default());
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(files)
+}
+
+/// Returns the directories owned by installed packages other than this one
+pub fn get_dirs_owned_by_others(package: &String) -> Result<HashSet<String>, Ps4Error> {
+    let mut dirs: HashSet<String> = HashSet::new();
+
+    for i in get_all_installed()?.into_iter().filter(|p| &p.name != package) {
+        dirs.extend(return_owned_files(&i.name)?.into_iter()
+            .filter(|f| f.file_type == FileType::Dir)
+            .map(|f| f.path));
+    }
+
+    Ok(dirs)
+}
+
+/// Removes a package and its files from the installed packages database
+pub fn remove_package_from_installed(package: &String) -> Result<(), Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_files_table(&conn)?;
+
+    let transaction = conn.unchecked_transaction()?;
+
+    transaction.execute("DELETE FROM installed_packages WHERE name = ?1",
+    params![package])?;
+
+    transaction.execute("DELETE FROM files WHERE package = ?1",
+    params![package])?;
+
+    transaction.commit()?;
+
+    Ok(())
+}
+
+pub fn update_cached_repos(repo: &String, repo_hash: &String) -> Result<(), Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    let current_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards?")
+        .as_millis()
+        .to_string();
+
+    conn.execute("
+        INSERT OR REPLACE INTO repos (name, repo_hash, last_updated)
+        VALUES (?1, ?2, ?3);",
+                 params![repo,
+                 repo_hash,
+                 current_time]
+    )?;
+
+    Ok(())
+}
+
+/// Get an installed package, None if it isn't installed
+pub fn get_installed_package(package: &String) -> Result<Option<InstalledPS4Packages>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    let installed = conn.query_row("SELECT * FROM installed_packages WHERE name = ?", [package], installed_from_row)
+        .optional()?;
+
+    Ok(installed)
+}
+
+/// Get a package from a repo, None if the repo doesn't have it or hasn't been synced
+pub fn get_remote_package(package: &String, repo: &String) -> Result<Option<PS4Package>, Ps4Error> {
+    let Some(conn) = open_repo(repo)? else {
+        return Ok(None);
+    };
+
+    let remote = conn.query_row("SELECT * FROM packages WHERE name = ?", [package], remote_from_row)
+        .optional()?;
+
+    Ok(remote)
+}
+
+/// Get all installed packages
+pub fn get_all_installed() -> Result<Vec<InstalledPS4Packages>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    let mut statement = conn.prepare("SELECT * FROM installed_packages")?;
+
+    let result = statement.query_map([], installed_from_row)?;
+
+    Ok(result.collect::<Result<Vec<InstalledPS4Packages>, rusqlite::Error>>()?)
+}
+
+/// Returns true if a package is in the group, instr() also matches substrings (base in base-devel)
+fn in_group(package: &PS4Package, group: &String) -> bool {
+    package.groups.split(",").any(|g| g == group)
+}
+
+/// Look for a group in a repo and return the repo it is present in
+pub fn search_for_group(group: &String) -> Result<Option<String>, Ps4Error> {
+    for i in get_enabled_repos() {
+        let Some(conn) = open_repo(&i.name)? else {
+            warning(format!("Repo {} is empty", i.name));
+            continue;
+        };
+
+        let mut statement = conn.prepare("SELECT * FROM packages WHERE instr(groups, ?) > 0;")?;
+
+        let result = statement.query_map([group], remote_from_row)?
+            .collect::<Result<Vec<PS4Package>, rusqlite::Error>>()?;
+
+        if result.iter().any(|p| in_group(p, group)) {
+            return Ok(Some(i.name.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Get all packages in a requested group the repo allows installing
+pub fn get_group(repo: &String, group: &String) -> Result<Vec<PS4Package>, Ps4Error> {
+    let repo_config = get_repo_config(repo);
+
+    let Some(conn) = open_repo(repo)? else {
+        return Ok(vec![]);
+    };
+
+    let mut statement = conn.prepare("SELECT * FROM packages WHERE instr(groups, ?) > 0;")?;
+
+    let result = statement.query_map([group], remote_from_row)?
+        .collect::<Result<Vec<PS4Package>, rusqlite::Error>>()?;
+
+    Ok(result.into_iter()
+        .filter(|p| in_group(p, group))
+        .filter(|p| repo_config.as_ref().map_or(true, |c| c.allows(&p.name)))
+        .collect())
+}
+
+/// Get all packages in a repo that provide the requested name
+pub fn get_provides(repo: &String, package: &String) -> Result<Vec<PS4Package>, Ps4Error> {
+    let Some(conn) = open_repo(repo)? else {
+        return Ok(vec![]);
+    };
+
+    let mut statement = conn.prepare("SELECT * FROM packages WHERE instr(provides, ?) > 0;")?;
+
+    let result = statement.query_map([package], remote_from_row)?
+        .collect::<Result<Vec<PS4Package>, rusqlite::Error>>()?;
+
+    // instr() also matches substrings (libfoo in libfoo2), so check the parsed atoms
+    Ok(result.into_iter()
+        .filter(|p| parse_atoms(&p.provides).iter().any(|a| &a.name == package))
+        .collect())
+}
+
+/// Get all installed packages that conflict with the requested package version
+pub fn get_conflicts(package: &String, version: &String) -> Result<Vec<InstalledPS4Packages>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    let mut statement = conn.prepare("SELECT * FROM installed_packages WHERE instr(conflicts, ?) > 0;")?;
+
+    let result = statement.query_map([package], installed_from_row)?
+        .collect::<Result<Vec<InstalledPS4Packages>, rusqlite::Error>>()?;
+
+    Ok(result.into_iter()
+        .filter(|p| p.conflicts.iter()
+            .map(|c| PackageAtom::parse(c))
+            .any(|a| &a.name == package && a.matches_version(version)))
+        .collect())
+}
+
+/// Get all installed packages that depend on the requested package
+pub fn get_depended_on(package: &String) -> Result<Vec<InstalledPS4Packages>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+
+    let mut statement = conn.prepare("SELECT * FROM installed_packages WHERE instr(dependencies, ?) > 0;")?;
+
+    let result = statement.query_map([package], installed_from_row)?
+        .collect::<Result<Vec<InstalledPS4Packages>, rusqlite::Error>>()?;
+
+    Ok(result.into_iter()
+        .filter(|p| p.dependencies.iter()
+            .map(|d| PackageAtom::parse(d))
+            .any(|a| &a.name == package))
+        .collect())
+}
+
+/// Get every package across all repos that is named or provides the requested name, in repo priority order
+pub fn get_candidates(package: &String) -> Result<Vec<(PS4Package, String)>, Ps4Error> {
+    let mut candidates: Vec<(PS4Package, String)> = vec![];
+
+    for i in get_enabled_repos() {
+        if let Some(pkg) = get_remote_package(package, &i.name)? {
+            if i.allows(&pkg.name) {
+                candidates.push((pkg, i.name.clone()));
+            }
+        }
+
+        for pkg in get_provides(&i.name, package)? {
+            if &pkg.name != package && i.allows(&pkg.name) {
+                candidates.push((pkg, i.name.clone()));
+            }
+        }
+    }
+
+    Ok(candidates)
+}
+
+/// Get every package in a repo, empty if the repo hasn't been synced
+pub fn get_all_remote(repo: &String) -> Result<Vec<PS4Package>, Ps4Error> {
+    let Some(conn) = open_repo(repo)? else {
+        warning(format!("Repo {} is empty", repo));
+        return Ok(vec![]);
+    };
+
+    let mut statement = conn.prepare("SELECT * FROM packages ORDER BY name;")?;
+
+    let result = statement.query_map([], remote_from_row)?;
+
+    Ok(result.collect::<Result<Vec<PS4Package>, rusqlite::Error>>()?)
+}
+/// Creates the history table, older databases don't have it yet
+fn create_history_table(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "create table if not exists history
+            (
+                id integer primary key autoincrement,
+                timestamp integer not null,
+                command text not null,
+                changes text not null
+            )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Records a transaction in the history table and returns its id
+pub fn add_history(command: &String, changes: &Vec<HistoryChange>) -> Result<i64, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_history_table(&conn)?;
+
+    let current_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards?")
+        .as_secs() as i64;
+
+    conn.execute("
+        INSERT INTO history (timestamp, command, changes)
+        VALUES (?1, ?2, ?3);",
+        params![current_time,
+        command,
+        serde_json::to_string(changes).map_err(|e| Ps4Error::Db(e.to_string()))?]
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// Returns every recorded transaction, oldest first
+pub fn get_history() -> Result<Vec<HistoryTransaction>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_history_table(&conn)?;
+
+    let mut statement = conn.prepare("SELECT id, timestamp, command, changes FROM history ORDER BY id")?;
+
+    let result = statement.query_map([], | transaction | {
+        return Ok(HistoryTransaction {
+            id: transaction.get(0)?,
+            timestamp: transaction.get(1)?,
+            command: transaction.get(2)?,
+            changes: serde_json::from_str(&transaction.get::<usize, String>(3)?).unwrap_or_default()
+        });
+    })?;
+
+    Ok(result.collect::<Result<Vec<HistoryTransaction>, rusqlite::Error>>()?)
+}
+
+/// Returns a single recorded transaction, None if there is no transaction with that id
+pub fn get_history_transaction(id: i64) -> Result<Option<HistoryTransaction>, Ps4Error> {
+    Ok(get_history()?.into_iter().find(|i| i.id == id))
+}
+
+/// Creates the files table, older databases don't have it yet and keep the files in installed_packages
+fn create_files_table(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "create table if not exists files
+            (
+                package text not null,
+                path text not null,
+                type text not null,
+                mode integer,
+                uid integer,
+                gid integer,
+                size integer,
+                mtime integer,
+                sha256 text,
+                primary key (package, path)
+            )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Creates the holds table, older databases don't have it yet
+fn create_holds_table(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "create table if not exists holds
+            (
+                name text not null unique primary key,
+                version text
+            )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Holds a package, replacing any existing hold on it
+pub fn add_hold(hold: &Hold) -> Result<(), Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_holds_table(&conn)?;
+
+    conn.execute("
+        INSERT OR REPLACE INTO holds (name, version)
+        VALUES (?1, ?2);",
+        params![hold.name, hold.version]
+    )?;
+
+    Ok(())
+}
+
+/// Removes the hold on a package, returns false if it wasn't held
+pub fn remove_hold(package: &String) -> Result<bool, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_holds_table(&conn)?;
+
+    let removed = conn.execute("DELETE FROM holds WHERE name = ?", [package])?;
+
+    Ok(removed > 0)
+}
+
+/// Returns every hold in the database, by name
+pub fn get_holds() -> Result<Vec<Hold>, Ps4Error> {
+    let conn = Connection::open(db_path("ps4"))?;
+    create_holds_table(&conn)?;
+
+    let mut statement = conn.prepare("SELECT name, version FROM holds ORDER BY name")?;
+
+    let result = statement.query_map([], | hold | {
+        return Ok(Hold {
+            name: hold.get(0)?,
+            version: hold.get(1)?
+        });
+    })?;
+
+    Ok(result.collect::<Result<Vec<Hold>, rusqlite::Error>>()?)
+}
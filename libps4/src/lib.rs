@@ -0,0 +1,61 @@
+/**************************************************************************/
+/* lib.rs                                                                 */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! The JaguarLinux package manager as a library.
+//!
+//! The `ps4` command is a thin front end over this crate, other tools can embed it the same way.
+//! [sync], [install], [remove] and [upgrade] change the root, [query] reads it. Everything returns
+//! a [Ps4Error] instead of ending the process. Each call gets the root, config and cache to work on,
+//! the prompt answers and the output settings as a [Context](context::Context). Signals are left to
+//! the embedder, the `ps4` command opts in with [handle_signals](ps4_lock_package::handle_signals).
+
+#![allow(clippy::all)]
+
+pub mod context;
+pub mod packaging;
+pub mod ps4_lock_package;
+pub mod database;
+/// Utility functions and structs relating to ps4's configuration.
+pub mod ps4_package_config;
+pub mod ps4mirror;
+pub mod ps4keyring;
+pub mod ps4cache;
+pub mod ps4download;
+pub mod ps4output;
+pub mod ps4error;
+pub mod ps4_package_progess_bar;
+pub mod ps4_package_manager_commands;
+pub mod ps4_package_transactions;
+pub mod query;
+
+pub use ps4error::Ps4Error;
+pub use ps4_package_manager_commands::install::{install, InstallOptions};
+pub use ps4_package_manager_commands::remove::remove;
+pub use ps4_package_manager_commands::sync::sync;
+pub use ps4_package_manager_commands::upgrade::upgrade;
+
+/// Get a static string of the current ps4 version
+pub fn get_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
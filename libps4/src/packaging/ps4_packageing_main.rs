@@ -0,0 +1,120 @@
+/**************************************************************************/
+/* ps4_packageing_main.rs                                                  */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::Path;
+
+use tar::Archive;
+use flate2::read::GzDecoder;
+
+use crate::{database::ps4dbmain::{get_dirs_owned_by_others, remove_package_from_installed, return_owned_files}, packaging::ps4_packageing_setup::PS4Package};
+use crate::database::ps4db::FileType;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::critical;
+use crate::ps4_package_progess_bar::root_path;
+use crate::ps4output::warning;
+
+pub fn decompress_gz(compressed_tar: File) -> Archive<GzDecoder<File>> {
+    return Archive::new(GzDecoder::new(compressed_tar));
+}
+
+pub fn decode_pkg_file(pkg: File) -> Result<PS4Package, Ps4Error> {
+    serde_json::from_reader(pkg).map_err(|e| Ps4Error::Verification(format!("Invalid PS4PKG file: {}", e)))
+}
+
+/// Read the PS4PKG file out of a package archive without unpacking it
+pub fn read_pkg_file(mut gztar: Archive<GzDecoder<File>>) -> Option<PS4Package> {
+    for file in gztar.entries().ok()? {
+        let file = file.ok()?;
+
+        if file.header().path().ok()? == Path::new("PS4PKG") {
+            return serde_json::from_reader(file).ok();
+        }
+    }
+
+    return None;
+}
+
+pub fn check_if_package(mut gztar: Archive<GzDecoder<File>>) -> bool {
+    let entries = match gztar.entries() {
+        Ok(entries) => entries,
+        Err(_) => return false
+    };
+
+    // Look for PKG file, anything that isn't a gzipped tar can't be a package
+    for file in entries {
+        match file.as_ref().map(|f| f.header().path()) {
+            // If a PKG file is found then this is a valid package
+            Ok(Ok(path)) if path == Path::new("PS4PKG") => return true,
+            Ok(Ok(_)) => {},
+            _ => return false
+        }
+    }
+
+    return false;
+}
+
+pub fn run_remove(package: &String) -> Result<(), Ps4Error> {
+    let files = return_owned_files(package)?;
+
+    // Directories stay while another installed package still owns them
+    let owned_elsewhere: HashSet<String> = get_dirs_owned_by_others(package)?;
+
+    // Deepest first, so a directory is emptied before its parent is looked at
+    let mut dirs: Vec<&String> = files.iter()
+        .filter(|f| f.file_type == FileType::Dir && !owned_elsewhere.contains(&f.path))
+        .map(|f| &f.path)
+        .collect();
+    dirs.sort_by_key(|d| Reverse(Path::new(d).components().count()));
+
+    // A package is only dropped from the database once all its files are gone
+    critical(|| {
+        for x in files.iter().filter(|f| f.file_type != FileType::Dir) {
+            let path = root_path(&x.path);
+
+            // symlink_metadata doesn't follow links, a symlink is unlinked even when its target is a directory or gone
+            match path.symlink_metadata() {
+                Ok(metadata) if metadata.is_dir() => warning(format!("{} is a directory now, leaving it.", x.path)),
+                Ok(_) => fs::remove_file(path)?,
+                Err(_) => {}
+            }
+        }
+
+        for x in &dirs {
+            let path = root_path(x);
+
+            let is_dir = path.symlink_metadata().map(|m| m.is_dir()).unwrap_or(false);
+
+            if is_dir && fs::read_dir(&path)?.next().is_none() {
+                fs::remove_dir(path)?;
+            }
+        }
+
+        remove_package_from_installed(package)?;
+
+        Ok(())
+    })
+}
@@ -23,6 +23,7 @@
  */
 
 use serde::Deserialize;
+use crate::database::ps4db::InstalledFile;
 
 #[derive(Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct PS4Package {
@@ -38,6 +39,8 @@ pub struct PS4Package {
     pub provides: String,
     pub conflicts: String,
     pub replaces: String,
+    /// Only present in repo databases, PS4PKG files don't carry their own checksum
+    #[serde(default)]
     pub sha512sum: String
 }
 
@@ -46,7 +49,7 @@ pub struct PS4NewPackage {
     pub groups: String,
     pub version: String,
     pub upstream: i32,
-    pub installed_files: Vec<String>,
+    pub installed_files: Vec<InstalledFile>,
     pub provides: Vec<String>,
     pub conflicts: Vec<String>,
     pub dependencies: Vec<String>
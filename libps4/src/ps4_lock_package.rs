@@ -0,0 +1,358 @@
+/**************************************************************************/
+/* ps4_lock_package.rs                                                    */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+use crate::ps4error::Ps4Error;
+use crate::ps4_package_progess_bar::{format_timestamp, prompt, root_path, tmp_path, PromptKind};
+use crate::ps4output::{info, warning};
+use crate::ps4_package_config::ps4_mirror_config_main::check_config;
+
+/// A lock held by this process, the kernel drops it when the file is closed or ps4 dies
+struct HeldLock {
+    /// None for a reader that couldn't lock, e.g. while a transaction is running
+    file: Option<File>,
+    exclusive: bool,
+    /// Number of guards sharing this lock, a command may run another one, e.g. upgrade runs install
+    guards: usize
+}
+
+static LOCK: Mutex<Option<HeldLock>> = Mutex::new(None);
+
+/// Temp directories of the running transaction, removed with its lock
+static WORKSPACES: Mutex<Vec<PathBuf>> = Mutex::new(vec![]);
+
+/// Sections that must not be cut short by a signal, e.g. moving files into place and recording them
+static CRITICAL: AtomicUsize = AtomicUsize::new(0);
+
+static SIGNALS: Once = Once::new();
+
+/// How long a writer waits for readers to finish before looking at who holds the lock
+const READER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the path of the lock file in the root
+pub fn lock_path() -> PathBuf {
+    root_path("/var/lib/ps4/lock")
+}
+
+/// The process that holds the exclusive lock, as recorded in the lock file
+struct LockOwner {
+    pid: u32,
+    command: String,
+    started: i64
+}
+
+impl LockOwner {
+    fn current() -> LockOwner {
+        LockOwner {
+            pid: std::process::id(),
+            command: env::args().collect::<Vec<String>>().join(" "),
+            started: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0)
+        }
+    }
+
+    /// Reads the owner from the lock file, None if it is empty or unreadable
+    fn read(file: &mut File) -> Option<LockOwner> {
+        let mut contents = String::new();
+
+        file.rewind().ok()?;
+        file.read_to_string(&mut contents).ok()?;
+
+        let mut lines = contents.lines();
+
+        Some(LockOwner {
+            pid: lines.next()?.parse().ok()?,
+            command: lines.next()?.to_string(),
+            started: lines.next()?.parse().ok()?
+        })
+    }
+
+    fn write(&self, file: &mut File) -> io::Result<()> {
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}\n{}\n{}\n", self.pid, self.command, self.started)?;
+        file.sync_all()
+    }
+
+    /// Returns true if the owning process still exists and is still running the same command
+    fn is_alive(&self) -> bool {
+        match fs::read(Path::new("/proc").join(self.pid.to_string()).join("cmdline")) {
+            Ok(cmdline) => String::from_utf8_lossy(&cmdline).trim_end_matches('\0').replace('\0', " ") == self.command,
+            Err(_) => false
+        }
+    }
+}
+
+impl std::fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "pid {}, `{}`, started {}", self.pid, self.command, format_timestamp(self.started))
+    }
+}
+
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    fs::create_dir_all(path.parent().unwrap())?;
+
+    OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)
+}
+
+/// Returns true if file is still the lock file at path, breaking a lock leaves whoever has it open with an unlinked file
+fn is_current(file: &File, path: &Path) -> io::Result<bool> {
+    let open = file.metadata()?;
+
+    match fs::metadata(path) {
+        Ok(current) => Ok(current.dev() == open.dev() && current.ino() == open.ino()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e)
+    }
+}
+
+/// Takes the exclusive lock for a command that changes the root.
+///
+/// Fails if another ps4 is changing the root. Readers are waited for. The kernel drops the lock when its
+/// owner dies, so a lock still held after that is open in some other process, e.g. a child that inherited
+/// it, and can be broken after asking.
+fn lock_exclusive() -> Result<File, Ps4Error> {
+    let path = lock_path();
+    let mut file = open_lock_file(&path)?;
+    let mut waited = Duration::ZERO;
+
+    loop {
+        match file.try_lock() {
+            // The lock may have been broken while waiting, then the file locked is no longer the lock file
+            Ok(()) if is_current(&file, &path)? => break,
+            Ok(()) => {
+                file = open_lock_file(&path)?;
+                continue;
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+            Err(TryLockError::WouldBlock) => {}
+        }
+
+        match LockOwner::read(&mut file) {
+            Some(owner) if owner.is_alive() => {
+                return Err(Ps4Error::Conflict(format!("Another instance of ps4 is already running ({}).", owner)));
+            }
+            owner if waited >= READER_TIMEOUT => {
+                match owner {
+                    Some(owner) => warning(format!("The lock is still held although its owner is gone ({}).", owner)),
+                    None => warning("The lock has been held by a reader for a long time.")
+                }
+
+                println!("Break the lock? (Only do this when no other ps4 is running)");
+                if !prompt(PromptKind::StaleLock)? {
+                    return Err(Ps4Error::Aborted);
+                }
+
+                // Whoever holds the old file keeps a lock nobody else looks at anymore. If someone else
+                // already broke it, the file at the path is theirs and stays
+                if is_current(&file, &path)? {
+                    fs::remove_file(&path)?;
+                }
+                file = open_lock_file(&path)?;
+                waited = Duration::ZERO;
+            }
+            _ => {
+                if waited.is_zero() {
+                    info("Waiting for other ps4 processes to finish reading...");
+                }
+
+                thread::sleep(Duration::from_millis(200));
+                waited += Duration::from_millis(200);
+            }
+        }
+    }
+
+    // A clean run empties the lock file, anything left in it is from a run that died
+    if let Some(owner) = LockOwner::read(&mut file) {
+        if owner.pid != std::process::id() {
+            warning(format!("A previous run of ps4 didn't finish ({}), its transaction may be incomplete.", owner));
+        }
+    }
+
+    LockOwner::current().write(&mut file)?;
+
+    Ok(file)
+}
+
+/// Takes a shared lock for a command that only reads the root.
+///
+/// Readers don't wait for a running transaction, they just note that what they show may change.
+fn lock_shared() -> Option<File> {
+    let path = lock_path();
+
+    // Without write access to the root there's nothing a reader could get in the way of
+    let mut file = File::open(&path).ok()?;
+
+    match file.try_lock_shared() {
+        Ok(()) if is_current(&file, &path).unwrap_or(false) => Some(file),
+        // Locked just as it was broken, the new lock file is the one writers look at
+        Ok(()) => lock_shared(),
+        Err(TryLockError::WouldBlock) => {
+            if let Some(owner) = LockOwner::read(&mut file) {
+                info(format!("ps4 is changing the root ({}), results may be out of date.", owner));
+            }
+            None
+        }
+        Err(TryLockError::Error(_)) => None
+    }
+}
+
+/// Removes the temp directories and releases the lock, emptying the lock file if it was the exclusive one
+fn release() {
+    for i in WORKSPACES.lock().unwrap().drain(..) {
+        let _ = fs::remove_dir_all(i);
+    }
+
+    if let Some(held) = LOCK.lock().unwrap().take() {
+        if let Some(file) = held.file {
+            if held.exclusive {
+                let _ = file.set_len(0);
+            }
+            let _ = file.unlock();
+        }
+    }
+}
+
+/// Releases the lock and temp directories on SIGINT and SIGTERM, once whatever can't be interrupted is done,
+/// then exits.
+///
+/// Only for programs that own the process like `ps4`, an embedder handles its own signals and drops the
+/// [TransactionGuard]s instead.
+pub fn handle_signals() {
+    SIGNALS.call_once(|| {
+        let mut signals = match Signals::new([SIGINT, SIGTERM]) {
+            Ok(signals) => signals,
+            Err(e) => {
+                warning(format!("Failed to handle signals: {}", e));
+                return;
+            }
+        };
+
+        thread::spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                while CRITICAL.load(Ordering::SeqCst) > 0 {
+                    thread::sleep(Duration::from_millis(50));
+                }
+
+                println!();
+                warning("Interrupted, cleaning up...");
+                release();
+
+                std::process::exit(128 + signal);
+            }
+        });
+    });
+}
+
+/// Held while a command works on the root. Owns the lock and the temp directories of the transaction
+/// and releases both when dropped, also when ps4 panics or is interrupted.
+pub struct TransactionGuard {
+    _private: ()
+}
+
+impl TransactionGuard {
+    /// Locks the root for a command that changes it
+    pub fn exclusive() -> Result<TransactionGuard, Ps4Error> {
+        check_config()?;
+
+        let mut lock = LOCK.lock().unwrap();
+
+        match lock.as_mut() {
+            Some(held) if held.exclusive => held.guards += 1,
+            Some(_) => return Err(Ps4Error::Invalid("Can't change the root while only reading it.".to_string())),
+            None => {
+                // Don't keep the lock mutex while waiting for other processes
+                drop(lock);
+                let file = lock_exclusive()?;
+                lock = LOCK.lock().unwrap();
+
+                *lock = Some(HeldLock { file: Some(file), exclusive: true, guards: 1 });
+            }
+        }
+
+        Ok(TransactionGuard { _private: () })
+    }
+
+    /// Locks the root for a command that only reads it
+    pub fn shared() -> Result<TransactionGuard, Ps4Error> {
+        check_config()?;
+
+        let mut lock = LOCK.lock().unwrap();
+
+        match lock.as_mut() {
+            Some(held) => held.guards += 1,
+            None => *lock = Some(HeldLock { file: lock_shared(), exclusive: false, guards: 1 })
+        }
+
+        Ok(TransactionGuard { _private: () })
+    }
+}
+
+impl Drop for TransactionGuard {
+    fn drop(&mut self) {
+        let last = match LOCK.lock().unwrap().as_mut() {
+            Some(held) => {
+                held.guards -= 1;
+                held.guards == 0
+            }
+            None => false
+        };
+
+        if last {
+            release();
+        }
+    }
+}
+
+/// Returns the temp directory of a package, removed again when the transaction ends
+pub fn workspace(package: &str) -> PathBuf {
+    let path = tmp_path(package);
+    let mut workspaces = WORKSPACES.lock().unwrap();
+
+    if !workspaces.contains(&path) {
+        workspaces.push(path.clone());
+    }
+
+    path
+}
+
+/// Runs f without letting SIGINT or SIGTERM stop ps4 halfway through it
+pub fn critical<T>(f: impl FnOnce() -> T) -> T {
+    CRITICAL.fetch_add(1, Ordering::SeqCst);
+    let result = f();
+    CRITICAL.fetch_sub(1, Ordering::SeqCst);
+
+    result
+}
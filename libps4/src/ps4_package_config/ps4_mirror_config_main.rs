@@ -0,0 +1,242 @@
+/**************************************************************************/
+/* ps4_mirror_config_main.rs                                               */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use crate::context::Context;
+use crate::ps4_package_config::ps4config_init::{ConfigEntries, ConfigError, ConfigFile, ConfigLoadError, Config, RepoConfig, RepoEntries};
+use crate::ps4_package_progess_bar::root_path;
+use crate::ps4error::Ps4Error;
+
+/// Every config read so far by path, each one is only read once per run, see [config].
+static CONFIGS: Mutex<Vec<(String, &'static Result<Config, ConfigLoadError>)>> = Mutex::new(vec![]);
+
+/// Used in place of an invalid config, so printing the error doesn't need a config
+static FALLBACK: OnceLock<Config> = OnceLock::new();
+
+/// Returns the path of the config file named by the [Context], defaults to /etc/ps4.d/ps4.conf under the root.
+pub fn config_path() -> String {
+    match Context::current().config {
+        Some(config) => config.to_string_lossy().to_string(),
+        None => root_path("/etc/ps4.d/ps4.conf").to_string_lossy().to_string(),
+    }
+}
+
+/// Returns the include directory read after the config file, `ps4.conf.d/` next to it.
+pub fn config_include_dir() -> PathBuf {
+    Path::new(&config_path()).with_file_name("ps4.conf.d")
+}
+
+/// Returns the path of the mirror list, one url template per line.
+pub fn mirrorlist_path() -> PathBuf {
+    root_path("/etc/ps4.d/mirrorlist")
+}
+
+/// Reads a single TOML config file.
+fn read_config_file(path: &Path) -> Result<ConfigFile, ConfigLoadError> {
+    let file = path.to_string_lossy().to_string();
+
+    let raw = fs::read_to_string(path).map_err(|e| ConfigLoadError { file: file.clone(), message: e.to_string() })?;
+
+    toml::from_str(&raw).map_err(|e| ConfigLoadError { file, message: e.to_string() })
+}
+
+/// Reads ps4.conf followed by every `*.conf` in ps4.conf.d/ in name order.
+///
+/// Later files override single values, repos are added or replace a repo of the same name, pins and holds are added.
+fn load_config() -> Result<Config, ConfigLoadError> {
+    let main_path = config_path();
+    let mut merged = read_config_file(Path::new(&main_path))?;
+
+    let mut includes: Vec<PathBuf> = match fs::read_dir(config_include_dir()) {
+        Ok(entries) => entries.flatten()
+            .map(|i| i.path())
+            .filter(|i| i.extension().is_some_and(|e| e == "conf"))
+            .collect(),
+        Err(_) => vec![],
+    };
+    includes.sort();
+
+    for i in includes {
+        let include = read_config_file(&i)?;
+
+        merged.architecture = include.architecture.or(merged.architecture);
+        merged.version = include.version.or(merged.version);
+        merged.codename = include.codename.or(merged.codename);
+        merged.disname = include.disname.or(merged.disname);
+        merged.colour = include.colour.or(merged.colour);
+        merged.progressbar = include.progressbar.or(merged.progressbar);
+        merged.parallel_downloads = include.parallel_downloads.or(merged.parallel_downloads);
+        merged.download_retries = include.download_retries.or(merged.download_retries);
+
+        for repo in include.repos {
+            match merged.repos.iter_mut().find(|x| x.name == repo.name) {
+                Some(existing) => *existing = repo,
+                None => merged.repos.push(repo),
+            }
+        }
+
+        merged.pins.extend(include.pins);
+        merged.hold.extend(include.hold);
+    }
+
+    let missing = |field: &str| ConfigLoadError { file: main_path.clone(), message: format!("missing field `{}`", field) };
+
+    Ok(Config {
+        architecture: merged.architecture.ok_or_else(|| missing("architecture"))?,
+        version: merged.version.ok_or_else(|| missing("version"))?,
+        codename: merged.codename.ok_or_else(|| missing("codename"))?,
+        disname: merged.disname.ok_or_else(|| missing("disname"))?,
+        colour: merged.colour.unwrap_or(false),
+        progressbar: merged.progressbar.unwrap_or(true),
+        parallel_downloads: merged.parallel_downloads.unwrap_or(4).max(1),
+        download_retries: merged.download_retries.unwrap_or(3),
+        repos: merged.repos,
+        pins: merged.pins,
+        hold: merged.hold
+    })
+}
+
+/// Returns the config of the current [Context], loading it if it hasn't been yet.
+///
+/// Loaded configs live as long as ps4, there is one for each config file used.
+fn loaded_config() -> &'static Result<Config, ConfigLoadError> {
+    let path = config_path();
+    let mut configs = CONFIGS.lock().unwrap();
+
+    if let Some((_, config)) = configs.iter().find(|(p, _)| p == &path) {
+        return config;
+    }
+
+    let config: &'static Result<Config, ConfigLoadError> = Box::leak(Box::new(load_config()));
+    configs.push((path, config));
+
+    config
+}
+
+/// Loads the config if it hasn't been yet and returns why it's invalid.
+///
+/// Taking a [TransactionGuard](crate::ps4_lock_package::TransactionGuard) checks the config,
+/// everything reading the config afterwards can rely on it being valid.
+pub fn check_config() -> Result<(), Ps4Error> {
+    match loaded_config() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Ps4Error::Config(e.to_string()))
+    }
+}
+
+/// Returns the config, loading it on first use. An invalid config reads as empty, see [check_config].
+pub(super) fn config() -> &'static Config {
+    match loaded_config() {
+        Ok(config) => config,
+        Err(_) => FALLBACK.get_or_init(Config::default)
+    }
+}
+
+/// Returns a string of the requested config entry, optionally returns a config entry within a repo.
+///
+/// See [ConfigEntries] and [RepoEntries].
+pub fn get_config_entry(entry: ConfigEntries, repo: Option<String>, repo_entry: Option<RepoEntries>) -> Result<String, ConfigError> {
+    let config = config();
+
+    match entry {
+        ConfigEntries::Disname => Ok(config.disname.clone()),
+        ConfigEntries::Codename => Ok(config.codename.clone()),
+        ConfigEntries::Version => Ok(config.version.clone()),
+        ConfigEntries::Architecture => Ok(config.architecture.clone()),
+        ConfigEntries::Colour => Ok(config.colour.to_string()),
+        ConfigEntries::Progressbar => Ok(config.progressbar.to_string()),
+        ConfigEntries::Repos => {
+            // Both a repo and a repo config entry are needed
+            let (Some(repo), Some(repo_entry)) = (repo, repo_entry) else {
+                return Err(ConfigError);
+            };
+
+            let repo = get_repo_config(&repo).ok_or(ConfigError)?;
+
+            match repo_entry {
+                RepoEntries::Name => Ok(repo.name),
+                RepoEntries::Active => Ok(repo.enabled.to_string()),
+                // If a url is not present, return an empty string
+                RepoEntries::Url => Ok(repo.url.unwrap_or_default()),
+            }
+        },
+    }
+}
+
+/// Returns the config of every repo, enabled or not, highest priority first.
+///
+/// Repos with the same priority keep the order of the config.
+pub fn get_repo_configs() -> Vec<RepoConfig> {
+    let config = config();
+
+    let mut repos: Vec<RepoConfig> = config.repos.iter()
+        .map(|i| RepoConfig::from_node(i.clone(), &config.architecture))
+        .collect();
+
+    repos.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+    return repos;
+}
+
+/// Returns the config of a single repo.
+pub fn get_repo_config(repo: &String) -> Option<RepoConfig> {
+    get_repo_configs().into_iter().find(|i| &i.name == repo)
+}
+
+/// Returns true if `colour` in the config asks for coloured output (default false).
+pub fn get_colour() -> bool {
+    config().colour
+}
+
+/// Returns true if `progressbar` in the config allows progress bars (default true).
+pub fn get_progressbar() -> bool {
+    config().progressbar
+}
+
+/// Returns how many packages may be downloaded at the same time, `parallel_downloads` in the config (default 4).
+pub fn get_parallel_downloads() -> usize {
+    config().parallel_downloads
+}
+
+/// Returns how often a failed download is retried on the same mirror, `download_retries` in the config (default 3).
+pub fn get_download_retries() -> u32 {
+    config().download_retries
+}
+
+/// Returns the repo a package is pinned to in the config, e.g. `nginx = "testing"` under `[pins]`.
+pub fn get_pinned_repo(package: &String) -> Option<String> {
+    config().pins.get(package).cloned()
+}
+
+/// Returns true if the config holds a package, e.g. `hold = ["linux"]`.
+pub fn is_held_in_config(package: &String) -> bool {
+    config().hold.contains(package)
+}
+
+/// Returns the config of the enabled repos, highest priority first.
+pub fn get_enabled_repos() -> Vec<RepoConfig> {
+    get_repo_configs().into_iter().filter(|i| i.enabled).collect()
+}
@@ -0,0 +1,199 @@
+/**************************************************************************/
+/* ps4config_init.rs                                                      */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::fmt::{self};
+use serde::Deserialize;
+
+/// Custom error type for config related errors.
+pub struct ConfigError;
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Requested config entry not found!")
+    }
+}
+
+impl fmt::Debug for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{ file: {}, line: {} }}", file!(), line!()) // programmer-facing output
+    }
+}
+
+/// All possible config entries.
+/// Reson whu i added allow dead code macros becuse the compiler thinks it not being used even tho it is being used and define 
+#[allow(dead_code)]
+pub enum ConfigEntries {
+    Disname,
+    Codename,
+    Version,
+    Architecture,
+    Colour,
+    Progressbar,
+    Repos
+}
+
+/// All possible repo config entries.
+
+#[allow(dead_code)]
+pub enum RepoEntries {
+    Name,
+    Active,
+    Url
+}
+
+/// Error loading the config, names the file and what's wrong with it.
+pub struct ConfigLoadError {
+    pub file: String,
+    pub message: String
+}
+
+impl fmt::Display for ConfigLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid config {}: {}", self.file, self.message.trim_end())
+    }
+}
+
+/// Struct form of ps4's config, merged from ps4.conf and ps4.conf.d/.
+#[derive(Default)]
+pub(super) struct Config {
+    pub(super) architecture: String,
+    pub(super) version: String,
+    pub(super) codename: String,
+    pub(super) disname: String,
+    pub(super) colour: bool,
+    pub(super) progressbar: bool,
+    /// How many packages are downloaded at the same time
+    pub(super) parallel_downloads: usize,
+    /// How often a download is retried on the same mirror before moving on
+    pub(super) download_retries: u32,
+    pub(super) repos: Vec<RepoNode>,
+    /// Package name to the only repo it may be installed from
+    pub(super) pins: BTreeMap<String, String>,
+    /// Packages that are never changed once installed
+    pub(super) hold: Vec<String>
+}
+
+/// A single config file, anything may be left out and set by another file.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ConfigFile {
+    pub(super) architecture: Option<String>,
+    pub(super) version: Option<String>,
+    pub(super) codename: Option<String>,
+    pub(super) disname: Option<String>,
+    pub(super) colour: Option<bool>,
+    pub(super) progressbar: Option<bool>,
+    pub(super) parallel_downloads: Option<usize>,
+    pub(super) download_retries: Option<u32>,
+    #[serde(default)]
+    pub(super) repos: Vec<RepoNode>,
+    #[serde(default)]
+    pub(super) pins: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(super) hold: Vec<String>
+}
+
+/// Struct form of repo config.
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RepoNode {
+    pub(super) name: String,
+    #[serde(alias = "enabled", default = "default_true")]
+    pub(super) active: bool,
+    pub(super) url: Option<String>,
+    /// Names of the keys in /etc/ps4.d/keys/ this repo's database may be signed with
+    #[serde(default)]
+    pub(super) keys: Vec<String>,
+    #[serde(default)]
+    pub(super) priority: i64,
+    pub(super) mirrors: Option<Vec<String>>,
+    #[serde(default)]
+    pub(super) signature: SignaturePolicy,
+    /// Architectures this repo serves, empty serves every architecture
+    #[serde(default)]
+    pub(super) architectures: Vec<String>,
+    pub(super) allowed_packages: Option<Vec<String>>
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How strictly a repo's database signature is checked when syncing.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SignaturePolicy {
+    /// The database must be signed by one of the repo's keys (default)
+    #[default]
+    Required,
+    /// A signature is checked when the repo has one, unsigned databases are accepted
+    Optional,
+    /// Signatures are never checked, only the hash
+    Never
+}
+
+/// Typed configuration of a single repo.
+#[derive(Clone)]
+pub struct RepoConfig {
+    pub name: String,
+    /// False when disabled in the config or when its architectures don't include the configured one
+    pub enabled: bool,
+    /// Custom url used instead of the mirrors
+    pub url: Option<String>,
+    /// Repos with a higher priority are preferred, 0 by default
+    pub priority: i64,
+    /// Mirror url templates used instead of /etc/ps4.d/mirrorlist
+    pub mirrors: Option<Vec<String>>,
+    pub signature: SignaturePolicy,
+    /// Names of the keys in /etc/ps4.d/keys/ trusted for this repo
+    pub keys: Vec<String>,
+    /// Packages that may be taken from this repo, None allows all of them
+    pub allowed_packages: Option<Vec<String>>
+}
+
+impl RepoConfig {
+    pub(super) fn from_node(node: RepoNode, architecture: &String) -> RepoConfig {
+        let serves_arch = node.architectures.is_empty() || node.architectures.contains(architecture);
+
+        RepoConfig {
+            name: node.name,
+            enabled: node.active && serves_arch,
+            url: node.url,
+            priority: node.priority,
+            mirrors: node.mirrors,
+            signature: node.signature,
+            keys: node.keys,
+            allowed_packages: node.allowed_packages
+        }
+    }
+
+    /// Returns true if the package may be installed from this repo
+    pub fn allows(&self, package: &String) -> bool {
+        match &self.allowed_packages {
+            Some(allowed) => allowed.contains(package),
+            None => true
+        }
+    }
+}
@@ -0,0 +1,134 @@
+/**************************************************************************/
+/* bootstrap.rs                                                           */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::fs;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::context::Context;
+use crate::database::ps4dbmain::init_database;
+use crate::ps4_package_config::ps4_mirror_config_main::{config_include_dir, config_path, mirrorlist_path};
+use crate::ps4_package_manager_commands::groupinstall::group_install;
+use crate::ps4_package_manager_commands::sync::sync;
+use crate::ps4_package_progess_bar::{db_path, require_root, root_path};
+use crate::ps4output::{action, error, info};
+use crate::ps4error::Ps4Error;
+
+/// Directories ps4 itself needs in a root
+const LAYOUT: [&str; 6] = ["/etc/ps4", "/etc/ps4.d/keys", "/etc/ps4.d/ps4.conf.d", "/tmp/ps4", "/var/cache/ps4", "/var/lib/ps4"];
+
+/// Config written when neither the target nor the host has one
+fn default_config() -> String {
+    format!("architecture = \"{}\"
+version = \"\"
+codename = \"\"
+disname = \"jaguarlinux\"
+colour = false
+progressbar = true
+parallel_downloads = 4
+download_retries = 3
+
+[[repos]]
+name = \"core\"
+active = true
+keys = [\"core\"]
+", env::consts::ARCH)
+}
+
+/// Copy the files of a host directory the target doesn't have yet, e.g. the trusted keys
+fn copy_missing(host_dir: &Path, target_dir: &Path) -> io::Result<()> {
+    let entries = match fs::read_dir(host_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+
+    for i in entries.flatten() {
+        let target = target_dir.join(i.file_name());
+
+        if i.path().is_file() && !target.exists() {
+            fs::copy(i.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn bootstrap(context: &Context, dir: String, groups: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+
+    // Where the config comes from has to be worked out before switching to the new root
+    let host_config = PathBuf::from(config_path());
+    let host_includes = config_include_dir();
+    let host_mirrorlist = mirrorlist_path();
+    let host_keys = root_path("/etc/ps4.d/keys");
+
+    let target = env::current_dir().map(|cwd| cwd.join(&dir)).unwrap_or(PathBuf::from(&dir));
+
+    // The new root has its own config and cache, the host's are only copied. Prompts and output stay as they are
+    let target_context = Context { root: Some(target.clone()), config: None, cache_dir: None, ..context.clone() };
+    let _target_context = target_context.enter();
+
+    action(format!("Bootstrapping {}...", target.display()));
+
+    for i in LAYOUT {
+        fs::create_dir_all(root_path(i))?;
+    }
+
+    fs::set_permissions(root_path("/tmp"), fs::Permissions::from_mode(0o1777))?;
+
+    copy_missing(&host_includes, &config_include_dir())?;
+    copy_missing(&host_keys, &root_path("/etc/ps4.d/keys"))?;
+
+    if host_mirrorlist.exists() && !mirrorlist_path().exists() {
+        fs::copy(&host_mirrorlist, mirrorlist_path())?;
+    }
+
+    let target_config = PathBuf::from(config_path());
+
+    if target_config.exists() {
+        info(format!("Keeping existing config {}", target_config.display()));
+    } else if host_config.exists() {
+        info(format!("Copying config from {}", host_config.display()));
+
+        fs::copy(&host_config, &target_config)?;
+    } else {
+        fs::write(&target_config, default_config())?;
+
+        error(format!("No config found at {}, wrote a default one to {}.", host_config.display(), target_config.display()));
+        return Err(Ps4Error::Config("Add mirrors and trusted keys for its repos and run bootstrap again.".to_string()));
+    }
+
+    if !db_path("ps4").exists() {
+        action("Initializing package database...");
+
+        init_database()?;
+    }
+
+    sync(&target_context)?;
+
+    group_install(&target_context, groups)
+}
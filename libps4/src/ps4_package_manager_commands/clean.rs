@@ -0,0 +1,146 @@
+/**************************************************************************/
+/* clean.rs                                                               */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+
+use version_compare::compare;
+
+use crate::context::Context;
+use crate::database::ps4dbmain::get_installed_package;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::{continue_prompt, require_root};
+use crate::ps4cache::{cache_dir, list_cached, CachedArchive};
+use crate::ps4output::{action, info, warning};
+
+/// Number of versions kept per package when no policy is given
+const DEFAULT_KEEP: usize = 3;
+
+/// Orders archives newest first
+fn newest_first(a: &CachedArchive, b: &CachedArchive) -> Ordering {
+    compare(&b.version, &a.version).ok()
+        .and_then(|c| c.ord())
+        .unwrap_or(Ordering::Equal)
+        .then(b.upstream.cmp(&a.upstream))
+}
+
+/// Options for `ps4 clean`
+#[derive(Default, Clone)]
+pub struct CleanOptions {
+    /// Keep the newest N versions of each package
+    pub keep: Option<usize>,
+
+    /// Remove every archive of packages that aren't installed
+    pub uninstalled: bool,
+
+    /// Remove every cached archive, interrupted downloads included
+    pub all: bool,
+}
+
+pub fn clean(context: &Context, options: CleanOptions) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let CleanOptions { keep, uninstalled, all: wipe } = options;
+    let mut keep = keep;
+
+    if !wipe && !uninstalled && keep.is_none() {
+        keep = Some(DEFAULT_KEEP);
+    }
+
+    require_root()?;
+    let _transaction = TransactionGuard::exclusive()?;
+
+    let mut packages: HashMap<String, Vec<CachedArchive>> = HashMap::new();
+
+    for i in list_cached() {
+        packages.entry(i.name.clone()).or_default().push(i);
+    }
+
+    let mut to_remove: Vec<CachedArchive> = vec![];
+
+    for (name, mut archives) in packages {
+        let installed = get_installed_package(&name)?;
+
+        if wipe || (uninstalled && installed.is_none()) {
+            to_remove.append(&mut archives);
+            continue;
+        }
+
+        if let Some(keep) = keep {
+            // Interrupted downloads stay to be resumed, only --all and --uninstalled remove them
+            archives.retain(|a| !a.partial);
+            archives.sort_by(newest_first);
+
+            let mut kept = 0;
+
+            for i in archives {
+                // The installed version always stays so it can be reinstalled offline
+                let is_installed = installed.as_ref()
+                    .map(|p| p.version == i.version && p.upstream == i.upstream)
+                    .unwrap_or(false);
+
+                if is_installed || kept < keep {
+                    kept += 1;
+                } else {
+                    to_remove.push(i);
+                }
+            }
+        }
+    }
+
+    if to_remove.is_empty() {
+        info("Nothing to clean.");
+
+        return Ok(());
+    }
+
+    let size: u64 = to_remove.iter().map(|a| a.size).sum();
+
+    println!("\nArchives to remove [{}]: {:.2} MiB\n", to_remove.len(), size as f64 / 1048576.0);
+
+    if !continue_prompt()? {
+        info("Abandoning clean!");
+
+        return Err(Ps4Error::Aborted);
+    }
+
+    for i in &to_remove {
+        if let Err(e) = fs::remove_file(&i.path) {
+            warning(format!("Failed to remove {}: {}", i.path.display(), e));
+        }
+    }
+
+    // Drop package directories that are now empty
+    if let Ok(entries) = fs::read_dir(cache_dir()) {
+        for i in entries.filter_map(|e| e.ok()) {
+            let _ = fs::remove_dir(i.path());
+        }
+    }
+
+    println!();
+    action("Complete! :)");
+
+    Ok(())
+}
@@ -23,53 +23,37 @@
  */
 
 use std::collections::HashSet;
-use crate::ps4::ps4_package_manager_commands::install::install;
-use crate::ps4::database::ps4dbmain::{get_group, search_for_group};
-use crate::ps4::ps4_lock_package::{create_lock, lock_exists, remove_lock};
+use crate::context::Context;
+use crate::ps4_package_manager_commands::install::{install, InstallOptions};
+use crate::database::ps4dbmain::{get_group, search_for_group};
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::require_root;
+use crate::ps4output::action;
 
-pub fn group_install(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Please provide a group to install. (Check ps4 --help for usage)");
+pub fn group_install(context: &Context, requested_groups: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
 
-        std::process::exit(1);
-    }
-
-    sudo::escalate_if_needed().expect("Failed to escalate to root.");
-
-    lock_exists();
+    // Install shares this lock, nothing can change the groups in between
+    let _transaction = TransactionGuard::exclusive()?;
 
-    create_lock().expect("Failed to create lock file. (Does /tmp/ps4.lock already exist?)");
-
-    let requested_groups: Vec<String> = args.clone().drain(2..).collect();
     let mut install_queue: HashSet<String> = HashSet::new();
 
     for i in requested_groups {
-        println!(" Looking for packages in {}", &i);
-
-        let group_repo = search_for_group(&i);
+        action(format!("Looking for packages in {}", &i));
 
-        if group_repo.is_err() {
-            eprintln!(" Group {} not found!", &i);
+        let group_repo = match search_for_group(&i)? {
+            Some(group_repo) => group_repo,
+            None => return Err(Ps4Error::NotFound(format!("Group {} not found!", &i)))
+        };
 
-            remove_lock().expect("Failed to remove lock?");
-            std::process::exit(1);
-        }
-
-        let requested_group = get_group(&group_repo.unwrap(), &i);
+        let requested_group = get_group(&group_repo, &i)?;
 
         for x in requested_group {
             install_queue.insert(x.name);
         }
     }
 
-    // Append padding to updates so install will accept it
-    let mut padding: Vec<String> = vec!["0".to_string(), "1".to_string()];
-    padding.append(&mut install_queue.into_iter().collect());
-
-    // Remove the lock as the install will takeover
-    remove_lock().expect("Failed to remove lock?");
-
-    install(padding);
-
-    // remove_lock is done by install
+    install(context, install_queue.into_iter().collect(), InstallOptions::default())
 }
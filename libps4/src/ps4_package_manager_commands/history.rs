@@ -0,0 +1,303 @@
+/**************************************************************************/
+/* history.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::fs::File;
+use std::path::PathBuf;
+
+use version_compare::{compare, Cmp};
+
+use crate::context::Context;
+use crate::database::ps4db::{HistoryAction, HistoryChange, HistoryTransaction, InstalledPS4Packages, Source};
+use crate::database::ps4dbmain::{add_history, get_depended_on, get_history, get_history_transaction, get_installed_package};
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4cache::find_cached_version;
+use crate::ps4_package_progess_bar::{continue_prompt, require_root, format_timestamp};
+use crate::packaging::ps4_packageing_main::{decompress_gz, read_pkg_file, run_remove};
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_transactions::install::{InstallTransaction, run_install};
+use crate::ps4_package_transactions::selection::blocking_hold;
+use crate::ps4output::{action, info, warning};
+
+/// Describes installing package over whatever version is currently installed
+pub fn install_change(package: &PS4Package, installed: Option<InstalledPS4Packages>) -> HistoryChange {
+    let new_version = format!("{}-{}", package.version, package.upstream);
+
+    let action = match &installed {
+        None => HistoryAction::Install,
+        Some(i) => match compare(&package.version, &i.version) {
+            Ok(Cmp::Gt) => HistoryAction::Upgrade,
+            Ok(Cmp::Lt) => HistoryAction::Downgrade,
+            _ if package.upstream > i.upstream => HistoryAction::Upgrade,
+            _ if package.upstream < i.upstream => HistoryAction::Downgrade,
+            _ => HistoryAction::Reinstall
+        }
+    };
+
+    HistoryChange {
+        name: package.name.clone(),
+        action,
+        old_version: installed.as_ref().map(|i| format!("{}-{}", i.version, i.upstream)),
+        new_version: Some(new_version),
+        old_source: installed.map(|i| i.source)
+    }
+}
+
+/// Describes removing an installed package
+pub fn remove_change(installed: &InstalledPS4Packages) -> HistoryChange {
+    HistoryChange {
+        name: installed.name.clone(),
+        action: HistoryAction::Remove,
+        old_version: Some(format!("{}-{}", installed.version, installed.upstream)),
+        new_version: None,
+        old_source: Some(installed.source.clone())
+    }
+}
+
+/// Records the changes of the current command in the history, failing to do so doesn't fail the command
+pub fn record_history(changes: &Vec<HistoryChange>) {
+    if changes.is_empty() {
+        return;
+    }
+
+    let command = std::iter::once("ps4".to_string())
+        .chain(env::args().skip(1))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    if let Err(e) = add_history(&command, changes) {
+        warning(format!("Failed to record transaction in history: {}", e));
+    }
+}
+
+fn summarize(transaction: &HistoryTransaction) -> String {
+    let count = |action: HistoryAction| transaction.changes.iter().filter(|c| c.action == action).count();
+
+    let mut summary: Vec<String> = vec![];
+
+    for (action, label) in [(HistoryAction::Install, "installed"), (HistoryAction::Remove, "removed"),
+                            (HistoryAction::Upgrade, "upgraded"), (HistoryAction::Downgrade, "downgraded"),
+                            (HistoryAction::Reinstall, "reinstalled")] {
+        if count(action) > 0 {
+            summary.push(format!("{} {}", count(action), label));
+        }
+    }
+
+    summary.join(", ")
+}
+
+fn show(id: i64) -> Result<(), Ps4Error> {
+    let transaction = match get_history_transaction(id)? {
+        Some(transaction) => transaction,
+        None => return Err(Ps4Error::NotFound(format!("Transaction {} not found!", id)))
+    };
+
+    println!("Transaction {}", transaction.id);
+    println!("Date            : {}", format_timestamp(transaction.timestamp));
+    println!("Command         : {}", transaction.command);
+    println!("Changes         : {}", summarize(&transaction));
+
+    for i in &transaction.changes {
+        match (&i.old_version, &i.new_version) {
+            (Some(old), Some(new)) if old != new => println!("\t{:<10} {} {} -> {}", i.action, i.name, old, new),
+            (_, Some(new)) => println!("\t{:<10} {} {}", i.action, i.name, new),
+            (Some(old), None) => println!("\t{:<10} {} {}", i.action, i.name, old),
+            (None, None) => println!("\t{:<10} {}", i.action, i.name),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn history(context: &Context, show_id: Option<i64>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    if let Some(id) = show_id {
+        return show(id);
+    }
+
+    let transactions = get_history()?;
+
+    if transactions.is_empty() {
+        println!("No transactions recorded.");
+        return Ok(());
+    }
+
+    for i in transactions {
+        println!("{:>5}  {}  {:<40}  {}", i.id, format_timestamp(i.timestamp), i.command, summarize(&i));
+    }
+
+    Ok(())
+}
+
+/// A single step needed to reverse a transaction
+enum UndoStep {
+    Remove(InstalledPS4Packages),
+    Install(PathBuf, Source)
+}
+
+/// Rebuild a Source from the source column of the installed database
+fn source_from_string(source: &String) -> Source {
+    match source.split_once(",") {
+        Some((name, url)) => Source { name: name.to_string(), url: Some(url.to_string()) },
+        None => Source { name: source.clone(), url: None }
+    }
+}
+
+pub fn undo(context: &Context, id: i64, ignore_holds: bool) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let transaction = match get_history_transaction(id)? {
+        Some(transaction) => transaction,
+        None => return Err(Ps4Error::NotFound(format!("Transaction {} not found!", id)))
+    };
+
+    require_root()?;
+    let _transaction = TransactionGuard::exclusive()?;
+
+    action(format!("Planning undo of transaction {}...", transaction.id));
+
+    let mut steps: Vec<UndoStep> = vec![];
+    let mut problems: Vec<String> = vec![];
+
+    for i in transaction.changes.iter().rev() {
+        if i.action == HistoryAction::Reinstall {
+            continue;
+        }
+
+        let installed = get_installed_package(&i.name)?;
+        let installed_version = installed.as_ref().map(|p| format!("{}-{}", p.version, p.upstream));
+
+        if installed_version != i.new_version {
+            problems.push(format!("{} has changed since transaction {}", i.name, transaction.id));
+            continue;
+        }
+
+        if i.action == HistoryAction::Install {
+            steps.push(UndoStep::Remove(installed.unwrap()));
+            continue;
+        }
+
+        let old_version = i.old_version.clone().unwrap_or_default();
+
+        // Holds match the version without its upstream
+        let version = old_version.rsplit_once("-").map(|v| v.0).unwrap_or(&old_version);
+
+        if !ignore_holds {
+            if let Some(hold) = blocking_hold(&i.name, version)? {
+                problems.push(format!("{} {} is not allowed by the hold {} (use --ignore-holds to override)", i.name, version, hold));
+                continue;
+            }
+        }
+
+        match find_cached_version(&i.name, &old_version) {
+            Some(archive) => steps.push(UndoStep::Install(archive, source_from_string(&i.old_source.clone().unwrap_or("local".to_string())))),
+            None => problems.push(format!("no cached archive for {} {}", i.name, old_version))
+        }
+    }
+
+    // Packages we remove must not be needed by anything that stays
+    let removing: Vec<String> = steps.iter()
+        .filter_map(|s| if let UndoStep::Remove(p) = s { Some(p.name.clone()) } else { None })
+        .collect();
+
+    for i in &removing {
+        for x in get_depended_on(i)? {
+            if !removing.contains(&x.name) {
+                problems.push(format!("{} is required by {}", i, x.name));
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(Ps4Error::Invalid(format!("Transaction {} can't be undone:\n\t{}", transaction.id, problems.join("\n\t"))));
+    }
+
+    if steps.is_empty() {
+        info("Nothing to undo.");
+
+        return Ok(());
+    }
+
+    for i in &steps {
+        match i {
+            UndoStep::Remove(p) => println!("\tremove  {} {}-{}", p.name, p.version, p.upstream),
+            UndoStep::Install(archive, _) => println!("\tinstall {}", archive.display()),
+        }
+    }
+
+    if !continue_prompt()? {
+        info("Abandoning undo!");
+
+        return Err(Ps4Error::Aborted);
+    }
+
+    let mut changes: Vec<HistoryChange> = vec![];
+
+    for i in steps {
+        match i {
+            UndoStep::Remove(p) => {
+                info(format!("Removing {} {}-{}...", &p.name, &p.version, &p.upstream));
+                if let Err(e) = run_remove(&p.name) {
+                    record_history(&changes);
+
+                    return Err(e);
+                }
+
+                changes.push(remove_change(&p));
+            },
+            UndoStep::Install(archive, source) => {
+                let package = match read_pkg_file(decompress_gz(File::open(&archive)?)) {
+                    Some(package) => package,
+                    None => {
+                        record_history(&changes);
+
+                        return Err(Ps4Error::Verification(format!("Cached archive {} is not a valid package!", archive.display())));
+                    }
+                };
+
+                info(format!("Installing {} v{}-{}...", &package.name, &package.version, &package.upstream));
+
+                let previous = get_installed_package(&package.name)?;
+                let change = install_change(&package, previous);
+
+                if let Err(e) = run_install(InstallTransaction { package, source }, File::open(&archive)?) {
+                    record_history(&changes);
+
+                    return Err(e);
+                }
+
+                changes.push(change);
+            }
+        }
+    }
+
+    record_history(&changes);
+
+    println!();
+    action("Complete! :)");
+
+    Ok(())
+}
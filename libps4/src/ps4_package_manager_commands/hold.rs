@@ -0,0 +1,101 @@
+/**************************************************************************/
+/* hold.rs                                                                */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::context::Context;
+use crate::database::ps4db::Hold;
+use crate::database::ps4dbmain::{add_hold, get_holds, get_installed_package, remove_hold};
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::require_root;
+use crate::ps4error::Ps4Error;
+use crate::ps4output::{action, warning};
+
+/// List every hold
+fn list_holds() -> Result<(), Ps4Error> {
+    let holds = get_holds()?;
+
+    if holds.is_empty() {
+        println!("No packages are held.");
+        return Ok(());
+    }
+
+    for i in holds {
+        println!("{}", i);
+    }
+
+    Ok(())
+}
+
+/// Hold a package, optionally only allowing versions that match a glob such as `13.*`
+pub fn hold(context: &Context, package: Option<String>, version: Option<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let package = match package {
+        Some(package) => package,
+        None => {
+            let _transaction = TransactionGuard::shared()?;
+            return list_holds();
+        }
+    };
+
+    require_root()?;
+    let _transaction = TransactionGuard::exclusive()?;
+
+    if get_installed_package(&package)?.is_none() {
+        warning(format!("{} is not installed, holding it anyway.", package));
+    }
+
+    let hold = Hold { name: package, version };
+
+    if let Err(e) = add_hold(&hold) {
+        return Err(e.context(format!("Failed to hold {}", hold.name)));
+    }
+
+    match &hold.version {
+        Some(version) => action(format!("Holding {}, only versions matching {} will be installed.", hold.name, version)),
+        None => action(format!("Holding {} at its installed version.", hold.name))
+    }
+
+    Ok(())
+}
+
+pub fn unhold(context: &Context, packages: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+    let _transaction = TransactionGuard::exclusive()?;
+
+    let mut not_held: Vec<String> = vec![];
+
+    for i in packages {
+        if remove_hold(&i).map_err(|e| e.context(format!("Failed to unhold {}", i)))? {
+            action(format!("{} is no longer held.", i));
+        } else {
+            not_held.push(i);
+        }
+    }
+
+    if !not_held.is_empty() {
+        return Err(Ps4Error::NotFound(format!("Not held: {}", not_held.join(", "))));
+    }
+
+    Ok(())
+}
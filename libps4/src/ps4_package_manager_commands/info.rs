@@ -0,0 +1,184 @@
+/**************************************************************************/
+/* info.rs                                                                */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs::File;
+use std::path::Path;
+
+use crate::context::Context;
+use crate::database::ps4dbmain::{get_depended_on, get_installed_package, return_owned_files};
+use crate::ps4_package_transactions::selection::{choose_package, get_hold, Choice};
+use crate::packaging::ps4_packageing_main::{decompress_gz, read_pkg_file};
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::database::ps4db::FileType;
+
+/// Joins names for an error message
+fn join(names: &Vec<&String>) -> String {
+    names.iter().map(|n| n.as_str()).collect::<Vec<&str>>().join(", ")
+}
+
+/// Formats a "," separated list for display
+fn display_list(list: &String) -> String {
+    let items: Vec<&str> = list.split(",").filter(|s| !s.is_empty()).collect();
+
+    if items.is_empty() {
+        return "None".to_string();
+    }
+
+    items.join("  ")
+}
+
+fn print_package(package: &PS4Package) {
+    println!("Name            : {}", package.name);
+    println!("Version         : {}-{}", package.version, package.upstream);
+    println!("Description     : {}", package.description);
+    println!("URL             : {}", package.url);
+    println!("Licenses        : {}", display_list(&package.license));
+    println!("Groups          : {}", display_list(&package.groups));
+    println!("Depends On      : {}", display_list(&package.depends));
+    println!("Optional Deps   : {}", display_list(&package.optional_depends));
+    println!("Provides        : {}", display_list(&package.provides));
+    println!("Conflicts With  : {}", display_list(&package.conflicts));
+    println!("Replaces        : {}", display_list(&package.replaces));
+}
+
+fn print_installed(package: &String) -> Result<(), Ps4Error> {
+    let Some(installed) = get_installed_package(package)? else {
+        println!("Installed       : No");
+        return Ok(());
+    };
+
+    let required_by: Vec<String> = get_depended_on(&installed.name)?.into_iter()
+        .filter(|p| p.name != installed.name)
+        .map(|p| p.name)
+        .collect();
+
+    println!("Installed       : {}-{}", installed.version, installed.upstream);
+    println!("Installed From  : {}", installed.source);
+    println!("Owned Files     : {}", return_owned_files(&installed.name)?.iter().filter(|f| f.file_type != FileType::Dir).count());
+    println!("Required By     : {}", display_list(&required_by.join(",")));
+
+    if let Some(hold) = get_hold(package)? {
+        println!("Held            : {}", hold);
+    }
+
+    Ok(())
+}
+
+/// Show which repo candidate was chosen and why
+fn print_choice(choice: &Choice) {
+    println!("Candidate Reason: {}", choice.reason);
+
+    let others: Vec<String> = choice.candidates.iter()
+        .skip(1)
+        .map(|c| format!("{} {}-{}", c.1, c.0.version, c.0.upstream))
+        .collect();
+
+    if !others.is_empty() {
+        println!("Other Candidates: {}", others.join("  "));
+    }
+}
+
+/// Show information about a local package archive, false if it isn't a valid package
+fn local_info(path: &String) -> Result<bool, Ps4Error> {
+    let Some(package) = read_pkg_file(decompress_gz(File::open(path)?)) else {
+        return Ok(false);
+    };
+
+    print_package(&package);
+    println!("Repository      : local ({})", path);
+    print_installed(&package.name)?;
+
+    Ok(true)
+}
+
+/// Show information about a package from the synced repos and the installed database, false if neither has it
+fn package_info(name: &String) -> Result<bool, Ps4Error> {
+    let choice = choose_package(name)?;
+
+    if let Some((package, repo)) = choice.candidates.first() {
+        print_package(package);
+        println!("Repository      : {}", repo);
+        print_choice(&choice);
+        print_installed(name)?;
+
+        return Ok(true);
+    }
+
+    // Not in any repo anymore, show what the installed database knows
+    let Some(installed) = get_installed_package(name)? else {
+        return Ok(false);
+    };
+
+    println!("Name            : {}", installed.name);
+    println!("Version         : {}-{}", installed.version, installed.upstream);
+    println!("Groups          : {}", display_list(&installed.groups.join(",")));
+    println!("Depends On      : {}", display_list(&installed.dependencies.join(",")));
+    println!("Provides        : {}", display_list(&installed.provides.join(",")));
+    println!("Conflicts With  : {}", display_list(&installed.conflicts.join(",")));
+    println!("Repository      : None");
+    println!("Candidate Reason: {}", choice.reason);
+    print_installed(name)?;
+
+    Ok(true)
+}
+
+pub fn info(context: &Context, packages: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    let mut invalid: Vec<&String> = vec![];
+    let mut missing: Vec<&String> = vec![];
+    let mut shown = 0;
+
+    for i in &packages {
+        if shown > 0 {
+            println!();
+        }
+
+        // Everything that can be shown is, what can't is reported once at the end
+        if i.ends_with(".tar.gz") && Path::new(i).exists() {
+            if !local_info(i)? {
+                invalid.push(i);
+                continue;
+            }
+        } else if !package_info(i)? {
+            missing.push(i);
+            continue;
+        }
+
+        shown += 1;
+    }
+
+    if !invalid.is_empty() {
+        return Err(Ps4Error::Invalid(format!("Not a valid package: {}", join(&invalid))));
+    }
+
+    if !missing.is_empty() {
+        return Err(Ps4Error::NotFound(format!("Package not found: {}", join(&missing))));
+    }
+
+    Ok(())
+}
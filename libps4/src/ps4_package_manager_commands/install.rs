@@ -0,0 +1,196 @@
+/**************************************************************************/
+/* install.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use crate::context::Context;
+use crate::database::ps4db::HistoryChange;
+use crate::database::ps4dbmain::get_installed_package;
+use crate::ps4_package_manager_commands::history::{install_change, record_history, remove_change};
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::{continue_prompt, display_installing_packages, prompt, PromptKind, require_root};
+use crate::ps4cache::cache_dir;
+use crate::ps4download::fetch_packages;
+use crate::packaging::ps4_packageing_main::run_remove;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_transactions::dependencies::resolve;
+use crate::ps4_package_transactions::install::{InstallTransaction, run_install};
+use crate::ps4output::{action, info, warning};
+
+/// Options for `ps4 install`
+#[derive(Default, Clone)]
+pub struct InstallOptions {
+    /// Install packages whose checksum can't be verified
+    pub allow_unverified: bool,
+
+    /// Only download the packages (and their dependencies) into the cache
+    pub download_only: bool,
+
+    /// Install only from already cached archives, never touch the network
+    pub offline: bool,
+
+    /// Change held packages anyway
+    pub ignore_holds: bool,
+}
+
+pub fn install(context: &Context, requested_packages: Vec<String>, options: InstallOptions) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let InstallOptions { allow_unverified, download_only, offline, ignore_holds } = options;
+
+    // Downloading into a directory of our own choosing doesn't need root
+    if !(download_only && context.cache_dir.is_some()) {
+        require_root()?;
+    }
+
+    // Downloading leaves the root alone, it only has to be read and may not even have a database
+    let transaction = if download_only { TransactionGuard::shared()? } else { TransactionGuard::exclusive()? };
+
+    action("Resolving packages and dependencies...");
+    let resolution = resolve(&requested_packages, download_only, ignore_holds)
+        .map_err(|e| e.context("Could not resolve dependencies"))?;
+
+    if resolution.install.is_empty() {
+        return Err(Ps4Error::NotFound("No packages in queue! Aborting...".to_string()));
+    }
+
+    action("Looking for package conflicts...");
+
+    if !resolution.replaces.is_empty() {
+        for (i, x) in &resolution.replaces {
+            println!();
+            info(format!("{} can be replaced with {}", x.name, i));
+
+            if !prompt(PromptKind::Replace)? {
+                return Err(Ps4Error::Conflict("Package conflicts detected. Aborting...".to_string()));
+            }
+        }
+
+        println!();
+    }
+
+    action("Installing the queue...");
+    let queue: HashMap<PS4Package, String> = resolution.install.clone().into_iter().collect();
+
+    let unverified: Vec<&PS4Package> = queue.keys().filter(|p| p.sha512sum.is_empty()).collect();
+
+    if !unverified.is_empty() {
+        for i in &unverified {
+            warning(format!("{} v{}-{} has no checksum in the repo database!", i.name, i.version, i.upstream));
+        }
+
+        if !allow_unverified {
+            return Err(Ps4Error::Verification("Refusing to install packages without a checksum. (Use --allow-unverified to override)".to_string()));
+        }
+    }
+
+    if download_only {
+        println!("\nPackages to download [{}]: {}\n", queue.len(), display_installing_packages(queue.clone()));
+    } else {
+        println!("\nPackages to install [{}]: {}\n", queue.len(), display_installing_packages(queue.clone()));
+    }
+
+    if !(continue_prompt()?) {
+        info("Abandoning install!");
+
+        return Err(Ps4Error::Aborted);
+    }
+
+    println!();
+    action("Downloading packages...");
+
+    let mut filequeue: HashMap<InstallTransaction, File> = HashMap::new();
+
+    let downloads: Vec<(PS4Package, String)> = queue.clone().into_iter().collect();
+
+    for (i, fetched) in downloads.iter().zip(fetch_packages(&downloads, offline)) {
+        match fetched {
+            Some((archive, source)) => {
+                filequeue.insert(InstallTransaction {
+                    package: i.0.clone(),
+                    source
+                }, File::open(&archive)?);
+            },
+            None => {
+                if offline {
+                    return Err(Ps4Error::Verification(format!("No verified copy of {} v{}-{} in {}! Aborting...", &i.0.name, &i.0.version, &i.0.upstream, cache_dir().display())));
+                } else {
+                    return Err(Ps4Error::Network(format!("Could not get a verified copy of {} from any mirror! Aborting...", &i.0.name)));
+                }
+            }
+        }
+    }
+
+    if download_only {
+        println!();
+        info(format!("Downloaded {} packages to {}", filequeue.len(), cache_dir().display()));
+        println!();
+        action("Complete! :)");
+
+        return Ok(());
+    }
+
+    let mut changes: Vec<HistoryChange> = vec![];
+
+    // Replaced packages are only removed once everything they're replaced with has been downloaded
+    for (_, x) in &resolution.replaces {
+        action(format!("Removing {}...", &x.name));
+        if let Err(e) = run_remove(&x.name) {
+            record_history(&changes);
+
+            return Err(e);
+        }
+
+        changes.push(remove_change(x));
+    }
+
+    println!();
+    action("Installing packages...");
+
+    for i in filequeue {
+        info(format!("Installing {} v{}-{}...", &i.0.package.name, &i.0.package.version, &i.0.package.upstream));
+
+        let change = install_change(&i.0.package, get_installed_package(&i.0.package.name)?);
+
+        if let Err(e) = run_install(i.0, i.1) {
+            record_history(&changes);
+
+            return Err(e);
+        }
+
+        changes.push(change);
+    }
+
+    record_history(&changes);
+
+    println!();
+    action("Cleaning up...");
+    drop(transaction);
+
+    println!();
+    action("Complete! :)");
+
+    Ok(())
+}
\ No newline at end of file
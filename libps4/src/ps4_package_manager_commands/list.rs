@@ -22,10 +22,13 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::ps4::database::ps4dbmain::get_all_installed;
+use crate::context::Context;
+use crate::query;
+use crate::ps4error::Ps4Error;
 
-pub fn list() {
-    let result = get_all_installed();
+pub fn list(context: &Context) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let result = query::installed(context)?;
 
     for i in result {
         let source = i.clone().source;
@@ -33,4 +36,6 @@ pub fn list() {
 
         println!("{} {}-{} {}", i.name, i.version, i.upstream, source_name);
     }
+
+    Ok(())
 }
@@ -0,0 +1,143 @@
+/**************************************************************************/
+/* localinstall.rs                                                        */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+use crate::context::Context;
+use crate::database::ps4db::{HistoryChange, Source};
+use crate::database::ps4dbmain::get_installed_package;
+use crate::ps4_package_manager_commands::history::{install_change, record_history};
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::{workspace, TransactionGuard};
+use crate::ps4_package_progess_bar::{continue_prompt, require_root};
+use crate::packaging::ps4_packageing_main::{check_if_package, decode_pkg_file, decompress_gz};
+use crate::ps4_package_transactions::install::{InstallTransaction, run_install};
+use crate::ps4_package_transactions::selection::blocking_hold;
+use crate::ps4output::{action, error, info, warning};
+
+pub fn local_install(context: &Context, packages: Vec<String>, ignore_holds: bool) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+    let transaction = TransactionGuard::exclusive()?;
+
+    action("Resolving packages...");
+    let mut package_queue: HashMap<InstallTransaction, File> = HashMap::new();
+    for i in &packages {
+        // Check if i is a valid path and assume it's a file we want to install if it is
+        if Path::new(i).exists() {
+            if !check_if_package(decompress_gz(fs::File::open(i)?)) {
+                warning(format!("{} is not a valid package!", i));
+                continue;
+            }
+
+            let mut package_tar = decompress_gz(fs::File::open(i)?);
+            let workspace = workspace(&Path::new(i).file_name().unwrap_or_default().to_string_lossy());
+            package_tar.unpack(&workspace)?;
+
+            let package = decode_pkg_file(fs::File::open(workspace.join("PS4PKG"))?)?;
+
+            package_queue.insert(InstallTransaction {
+                package: package,
+                source: Source{ name: "local".to_string(), url: None }
+            }, fs::File::open(i)?);
+        } else {
+            warning(format!("{} is not a valid package!", i));
+        }
+    }
+
+    let mut temp_string = String::new();
+
+    if package_queue.is_empty() {
+        return Err(Ps4Error::Invalid("No packages to install!".to_string()));
+    }
+
+    // Local archives don't go through the resolver, which is where holds are normally enforced
+    if !ignore_holds {
+        let mut held: Vec<String> = vec![];
+
+        for (i, _f) in &package_queue {
+            if let Some(hold) = blocking_hold(&i.package.name, &i.package.version)? {
+                held.push(format!("{} {} is not allowed by the hold {}", i.package.name, i.package.version, hold));
+            }
+        }
+
+        if !held.is_empty() {
+            error("Held packages would be changed:");
+            for i in held {
+                eprintln!("\t{}", i);
+            }
+
+            return Err(Ps4Error::Conflict("Refusing to change held packages. (Use --ignore-holds to override)".to_string()));
+        }
+    }
+
+    for (i, _f) in &package_queue {
+        temp_string.push_str(&*i.package.name);
+        temp_string.push_str("->");
+        temp_string.push_str(&*i.package.version);
+        temp_string.push_str("->");
+        temp_string.push_str(&*i.package.upstream.to_string());
+        temp_string.push_str(" ");
+    }
+
+    println!("\nPackages to install [{}]: {}\n", &package_queue.len(), temp_string);
+
+    if !(continue_prompt()?) {
+        info("Abandoning install!");
+
+        return Err(Ps4Error::Aborted);
+    }
+
+    println!();
+    action("Installing packages...");
+    let mut changes: Vec<HistoryChange> = Vec::new();
+    for (i, f) in package_queue {
+        info(format!("Installing {} v{}-{}...", &i.package.name, &i.package.version, &i.package.upstream));
+
+        let change = install_change(&i.package, get_installed_package(&i.package.name)?);
+
+        if let Err(e) = run_install(i, f) {
+            record_history(&changes);
+
+            return Err(e);
+        }
+
+        changes.push(change);
+    }
+
+    record_history(&changes);
+
+    println!();
+    action("Cleaning up...");
+    drop(transaction);
+
+    println!();
+    action("Complete! :)");
+
+    Ok(())
+}
@@ -22,7 +22,6 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-pub mod help;
 pub mod sync;
 pub mod upgrade;
 pub mod install;
@@ -30,3 +29,10 @@ pub mod localinstall;
 pub mod remove;
 pub mod list;
 pub mod groupinstall;
+pub mod info;
+pub mod search;
+pub mod history;
+pub mod clean;
+pub mod bootstrap;
+pub mod hold;
+pub mod verify;
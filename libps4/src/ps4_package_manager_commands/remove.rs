@@ -25,50 +25,43 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::ps4::{ps4_lock_package::{create_lock, lock_exists, remove_lock}, packaging::ps4_packageing_main::run_remove};
-use crate::ps4::database::ps4dbmain::{get_depended_on, get_installed_package};
-use crate::ps4::database::ps4db::InstalledPS4Packages;
-use crate::ps4::ps4_package_progess_bar::{continue_prompt, display_removing_packages};
-
-pub fn remove(args: Vec<String>) {
-    if args.len() < 3 {
-        eprintln!("Please provide a path to a package to remove. (Check ps4 --help for usage)");
-
-        std::process::exit(1);
-    }
-
-    sudo::escalate_if_needed().expect("Failed to escalate to root.");
-    lock_exists();
-    create_lock().expect("Failed to create lock file. (Does /tmp/ps4.lock already exist?)");
-
-    println!(" removeing  ps4 packages...");
-    let raw_packages: Vec<String> = args.clone().drain(2..).collect();
+use crate::context::Context;
+use crate::{ps4_lock_package::TransactionGuard, packaging::ps4_packageing_main::run_remove};
+use crate::database::ps4dbmain::{get_depended_on, get_installed_package};
+use crate::database::ps4db::{HistoryChange, InstalledPS4Packages};
+use crate::ps4_package_manager_commands::history::{record_history, remove_change};
+use crate::ps4_package_progess_bar::{continue_prompt, display_removing_packages, require_root};
+use crate::ps4error::Ps4Error;
+use crate::ps4output::{action, error, info, warning};
+
+pub fn remove(context: &Context, raw_packages: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+    let _transaction = TransactionGuard::exclusive()?;
+
+    action("Resolving packages to remove...");
     let mut packages: HashSet<InstalledPS4Packages> = HashSet::new();
 
     for i in raw_packages {
-        let package = get_installed_package(&i);
-
-        if package.is_ok() {
-            packages.insert(package.unwrap());
-        } else {
-            println!("WARNING Package {} not found.", i);
+        match get_installed_package(&i)? {
+            Some(package) => {
+                packages.insert(package);
+            },
+            None => warning(format!("Package {} not found.", i))
         }
     }
 
     if packages.is_empty() {
-        println!("ERROR No valid packages specified!");
-
-        remove_lock().expect("Failed to remove lock file.");
-        std::process::exit(1);
+        return Err(Ps4Error::NotFound("No valid packages specified!".to_string()));
     }
 
-    println!(" Checking dependencies...");
+    action("Checking dependencies...");
     let mut abort = false;
     let mut abort_map: HashMap<InstalledPS4Packages, Vec<InstalledPS4Packages>> = HashMap::new();
     for i in packages.clone() {
         let mut abort_vec: Vec<InstalledPS4Packages> = Vec::new();
 
-        for x in get_depended_on(&i.name) {
+        for x in get_depended_on(&i.name)? {
             abort = true;
             abort_vec.push(x);
         }
@@ -77,7 +70,7 @@ pub fn remove(args: Vec<String>) {
     }
 
     if abort {
-        println!("ERROR The following packages are depended on by other packages:");
+        error("The following packages are depended on by other packages:");
         for (i, v) in abort_map.iter() {
             println!("{} {}-{} is required by:", i.name, i.version, i.upstream);
             for x in v {
@@ -85,28 +78,38 @@ pub fn remove(args: Vec<String>) {
             }
         }
 
-        println!("ERROR Please remove the above packages before continuing.");
-        remove_lock().expect("Failed to remove lock file.");
-        std::process::exit(1);
+        return Err(Ps4Error::Dependency("Please remove the above packages before continuing.".to_string()));
     }
 
     println!("\nPackages to remove [{}]: {}\n", packages.len(), display_removing_packages(packages.clone()));
 
-    if !continue_prompt() {
-        println!("\n==> Aborting!");
+    if !continue_prompt()? {
+        println!();
+        info("Abandoning remove!");
 
-        remove_lock().expect("Failed to remove lock file.");
-        std::process::exit(0);
+        return Err(Ps4Error::Aborted);
     }
 
-    println!("\n Removing packages...");
+    println!();
+    action("Removing packages...");
+
+    let mut changes: Vec<HistoryChange> = Vec::new();
 
     for i in packages {
-        println!("=> Removing {} {}-{}...", &i.name, &i.version, &i.upstream);
-        run_remove(&i.name);
+        info(format!("Removing {} {}-{}...", &i.name, &i.version, &i.upstream));
+        if let Err(e) = run_remove(&i.name) {
+            record_history(&changes);
+
+            return Err(e);
+        }
+
+        changes.push(remove_change(&i));
     }
 
-    println!("\n Complete!");
+    record_history(&changes);
+
+    println!();
+    action("Complete!");
 
-    remove_lock().expect("Failed to remove lock");
+    Ok(())
 }
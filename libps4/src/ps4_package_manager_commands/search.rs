@@ -0,0 +1,65 @@
+/**************************************************************************/
+/* search.rs                                                              */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Ordering;
+
+use crate::context::Context;
+use crate::database::ps4dbmain::get_installed_package;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_transactions::selection::compare_releases;
+use crate::query::{self, SearchOptions};
+
+/// Returns the installed marker for a search hit
+fn installed_marker(package: &PS4Package) -> Result<String, Ps4Error> {
+    let Some(installed) = get_installed_package(&package.name)? else {
+        return Ok(String::new());
+    };
+
+    // Ordered the same way candidates are ranked, version first, then upstream
+    if compare_releases((&package.version, package.upstream), (&installed.version, installed.upstream)) == Ordering::Greater {
+        return Ok(format!(" [installed: {}-{}, upgradable]", installed.version, installed.upstream));
+    }
+
+    Ok(" [installed]".to_string())
+}
+
+pub fn search(context: &Context, options: SearchOptions) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    let hits = query::search(context, &options)?;
+
+    for (package, repo) in &hits {
+        println!("{}/{} {}-{}{}", repo, package.name, package.version, package.upstream, installed_marker(package)?);
+        println!("    {}", package.description);
+    }
+
+    if hits.is_empty() {
+        return Err(Ps4Error::NotFound("No packages found.".to_string()));
+    }
+
+    Ok(())
+}
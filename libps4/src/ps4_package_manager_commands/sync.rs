@@ -0,0 +1,209 @@
+/**************************************************************************/
+/* sync.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs::File;
+use std::io::{copy, Read};
+
+use isahc::http::StatusCode;
+use ring::digest::{self, SHA512};
+
+use hex::ToHex;
+
+use crate::context::Context;
+use crate::ps4_package_config::ps4_mirror_config_main::get_enabled_repos;
+use crate::ps4_package_config::ps4config_init::SignaturePolicy;
+use crate::ps4keyring::verify_signature;
+use crate::database::ps4dbmain::update_cached_repos;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::{db_path, require_root, get};
+use crate::ps4mirror::load_mirrors;
+
+use isahc::prelude::*;
+use isahc::{Body, Response};
+use crate::ps4output::{action, error, info, warning};
+
+pub fn sync(context: &Context) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+
+    let _transaction = TransactionGuard::exclusive()?;
+
+    action("Synchronizing repo databases...");
+
+    // Repos left unsynced, and whether any of them was refused rather than unreachable
+    let mut failed: Vec<String> = vec![];
+    let mut rejected = false;
+
+    for i in get_enabled_repos() {
+        info(format!("Updating {}", i.name));
+
+        let trusted_keys = &i.keys;
+
+        if trusted_keys.is_empty() && i.signature == SignaturePolicy::Required {
+            error(format!("No trusted keys configured for {}, refusing to sync it.", i.name));
+            failed.push(i.name.clone());
+            rejected = true;
+            continue;
+        }
+
+        let mirror_list = load_mirrors(&i);
+
+        if mirror_list.is_empty() {
+            warning(format!("No mirrors configured for {}, skipping it.", i.name));
+            continue;
+        }
+
+        let mut synced = false;
+
+        for x in mirror_list {
+            let url = format!("{}/ps4.db", x);
+
+            let db_response = get(&url);
+
+            if db_response.is_err() {
+                warning(format!("Failed to get {}. Error: {}", &url, db_response.err().unwrap()));
+                continue;
+            }
+
+            let mut db_response_unwrap: Response<Body> = db_response.expect("Response errored while bypassing the check");
+
+            if db_response_unwrap.status() != StatusCode::OK  {
+                warning(format!("Failed to get {}. Status: {}", &url, db_response_unwrap.status()));
+                continue;
+            }
+
+            let content_bytes = match db_response_unwrap.bytes() {
+                Ok(content_bytes) => content_bytes,
+                Err(e) => {
+                    warning(format!("Failed to get {}. Error: {}", &url, e));
+                    continue;
+                }
+            };
+            let mut content = content_bytes.as_slice();
+            let mut content_save = content;
+
+            let hash_url = format!("{}/ps4.hash", x);
+
+            let hash_response = get(&hash_url);
+
+            if hash_response.is_err() {
+                warning(format!("Failed to get {}. Error: {}", &hash_url, hash_response.err().unwrap()));
+                continue;
+            }
+
+            let mut hash_response_unwrap: Response<Body> = hash_response.expect("Response errored while bypassing the check");
+
+            if hash_response_unwrap.status() != StatusCode::OK  {
+                warning(format!("Failed to get {}. Status: {}", &hash_url, hash_response_unwrap.status()));
+                continue;
+            }
+
+            let hash_string = match hash_response_unwrap.text() {
+                Ok(hash_string) => hash_string,
+                Err(e) => {
+                    warning(format!("Failed to get {}. Error: {}", &hash_url, e));
+                    continue;
+                }
+            };
+
+            let mut hash_context = digest::Context::new(&SHA512);
+            let mut buffer = [0; 1024];
+
+            loop {
+                let read = content.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                hash_context.update(&buffer[..read]);
+            }
+
+            let generated_hash = hash_context.finish();
+
+            if generated_hash.as_ref().encode_hex::<String>() != hash_string {
+                warning(format!("Verification failed for {}, trying next mirror.", hash_url));
+                rejected = true;
+                continue;
+            }
+
+            // An optional signature can only be checked when the repo trusts some keys
+            let check_signature = match i.signature {
+                SignaturePolicy::Required => true,
+                SignaturePolicy::Optional => !trusted_keys.is_empty(),
+                SignaturePolicy::Never => false,
+            };
+
+            if check_signature {
+                let sig_url = format!("{}/ps4.sig", x);
+
+                let sig_response = get(&sig_url);
+
+                if sig_response.is_err() {
+                    warning(format!("Failed to get {}. Error: {}", &sig_url, sig_response.err().unwrap()));
+                    continue;
+                }
+
+                let mut sig_response_unwrap: Response<Body> = sig_response.expect("Response errored while bypassing the check");
+
+                if sig_response_unwrap.status() == StatusCode::NOT_FOUND && i.signature == SignaturePolicy::Optional {
+                    warning(format!("{} is not signed, accepting it as its signature is optional.", i.name));
+                } else if sig_response_unwrap.status() != StatusCode::OK  {
+                    warning(format!("Failed to get {}. Status: {}", &sig_url, sig_response_unwrap.status()));
+                    continue;
+                } else {
+                    let signature = sig_response_unwrap.text().unwrap_or_default();
+
+                    if !verify_signature(hash_string.as_bytes(), &signature, trusted_keys) {
+                        warning(format!("Signature verification failed for {}, trying next mirror.", sig_url));
+                        rejected = true;
+                        continue;
+                    }
+                }
+            }
+
+            let mut dest = File::create(db_path(&i.name))?;
+            copy(&mut content_save, &mut dest)?;
+
+            update_cached_repos(&i.name, &hash_string)?;
+
+            synced = true;
+            break;
+        }
+
+        if !synced {
+            error(format!("{} could not be synced from any mirror.", i.name));
+            failed.push(i.name.clone());
+        }
+    }
+
+    if !failed.is_empty() {
+        let message = format!("Failed to sync {}.", failed.join(", "));
+
+        return Err(if rejected { Ps4Error::Verification(message) } else { Ps4Error::Network(message) });
+    }
+
+    action("Synchronization complete!");
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+/**************************************************************************/
+/* upgrade.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::context::Context;
+use crate::ps4_package_manager_commands::install::{install, InstallOptions};
+use crate::query;
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_progess_bar::require_root;
+use crate::ps4output::{action, info};
+
+pub fn upgrade(context: &Context) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    require_root()?;
+
+    // Sync and install share this lock, the databases can't change in between
+    let _transaction = TransactionGuard::exclusive()?;
+
+    // Ensure databases are synced
+    crate::ps4_package_manager_commands::sync::sync(context)?;
+
+    action("Checking for updates...");
+
+    let mut updates: Vec<String> = Vec::new();
+    let mut held: Vec<String> = Vec::new();
+
+    for i in query::updates(context)? {
+        match i.held_by {
+            Some(hold) => held.push(format!("{} ({}-{} available, held by {})", i.installed.name, i.available.version, i.available.upstream, hold)),
+            None => updates.push(i.installed.name)
+        }
+    }
+
+    if !held.is_empty() {
+        info(format!("Held back: {}", held.join(", ")));
+    }
+
+    match updates.len() {
+        0 => {
+            info("No updates found.");
+
+            return Ok(());
+        },
+        1 => {
+            info(format!("Updating {} package...", updates.len()));
+        },
+        _ => {
+            info(format!("Updating {} packages...", updates.len()));
+        }
+    }
+
+    install(context, updates, InstallOptions::default())
+}
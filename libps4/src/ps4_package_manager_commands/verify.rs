@@ -1,5 +1,5 @@
 /**************************************************************************/
-/* ps4db.rs                                                                */
+/* verify.rs                                                              */
 /**************************************************************************/
 /*                         This file is part of:                          */
 /*                           PS4 PACKGE MANAGER                           */
@@ -22,37 +22,28 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-pub struct _PS4pkgstructer {
-    pub name: String,
-    pub version: String,
-    pub upstream: i32,
-    pub description: String,
-    pub groups: Vec<String>,
-    pub url: String,
-    pub license: Vec<String>,
-    pub depends: Vec<String>,
-    pub optional_depends: Vec<String>,
-    pub provides: Vec<String>,
-    pub conflicts: Vec<String>,
-    pub replaces: Vec<String>,
-    pub sha512sum: String
-}
+use crate::context::Context;
+use crate::query;
+use crate::ps4_package_progess_bar::require_root;
+use crate::ps4error::Ps4Error;
+use crate::ps4output::{action, info};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct InstalledPS4Packages {
-    pub name: String,
-    pub groups: Vec<String>,
-    pub source: String,
-    pub version: String,
-    pub upstream: i32,
-    pub installed_files: Vec<String>,
-    pub provides: Vec<String>,
-    pub conflicts: Vec<String>,
-    pub dependencies: Vec<String>
-}
+pub fn verify(context: &Context, packages: Vec<String>) -> Result<(), Ps4Error> {
+    let _context = context.enter();
+    // Files only root can read have to be compared too
+    require_root()?;
+
+    action("Verifying installed files...");
+    let issues = query::verify(context, &packages)?;
 
-#[derive(PartialEq, Eq, Hash, Clone)]
-pub struct Source {
-    pub name: String,
-    pub url: Option<String>
-}
\ No newline at end of file
+    if issues.is_empty() {
+        info("No problems found.");
+        return Ok(());
+    }
+
+    for i in &issues {
+        println!("{}: {} {}", i.package, i.path, i.problem);
+    }
+
+    Err(Ps4Error::Verification(format!("{} problem(s) found in installed files!", issues.len())))
+}
@@ -0,0 +1,348 @@
+/**************************************************************************/
+/* ps4_package_progess_bar.rs                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use isahc::{Body, Request, Response};
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+use crate::context::Context;
+use crate::database::ps4db::InstalledPS4Packages;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_config::ps4_mirror_config_main::get_progressbar;
+use crate::ps4output::{colour_enabled, format_message, info, message, Level};
+use crate::ps4error::Ps4Error;
+
+/// Converts a vec of strings to a flat string separated by ","
+pub fn vec_to_string(vec: Vec<String>) -> String {
+    let mut temp_string: String = String::new();
+    let mut x: usize = 0;
+    for i in &vec {
+        temp_string.push_str(&*i);
+        if !(x == (&vec.len() - 1)) {
+            temp_string.push_str(",");
+        }
+        x += 1;
+    }
+    temp_string
+}
+
+//cool progress bar
+pub fn display_installing_packages(set: HashMap<PS4Package, String>) -> String {
+    let mut temp_string: String = String::new();
+    for i in set {
+        temp_string.push_str(&*i.0.name);
+        temp_string.push_str("<-");
+        temp_string.push_str(&*i.0.version);
+        temp_string.push_str("<-");
+        temp_string.push_str(&*i.0.upstream.to_string());
+        temp_string.push_str(" ");
+    }
+    temp_string
+}
+
+pub fn display_removing_packages(set: HashSet<InstalledPS4Packages>) -> String {
+    let mut temp_string: String = String::new();
+    for i in set {
+        temp_string.push_str(&*i.name);
+        temp_string.push_str("->");
+        temp_string.push_str(&*i.version);
+        temp_string.push_str("->");
+        temp_string.push_str(&*i.upstream.to_string());
+        temp_string.push_str(" ");
+    }
+    temp_string
+}
+
+/// Returns true if progress bars should be drawn, only on a terminal and when the config allows them
+pub fn progress_enabled() -> bool {
+    io::stdout().is_terminal() && get_progressbar()
+}
+
+/// Progress of all downloads of a run, one bar per download and one for the whole run.
+///
+/// Everything is hidden when progress bars are disabled.
+pub struct DownloadProgress {
+    bars: MultiProgress,
+    total: ProgressBar
+}
+
+impl DownloadProgress {
+    pub fn new(count: usize) -> DownloadProgress {
+        let bars = MultiProgress::with_draw_target(if progress_enabled() {
+            ProgressDrawTarget::stdout()
+        } else {
+            ProgressDrawTarget::hidden()
+        });
+
+        // The size of the run is only known once every download has started
+        let total = bars.add(ProgressBar::new(0));
+        total.set_style(ProgressStyle::with_template(" {msg:24!} [{bar:30}] {bytes}/{total_bytes} {binary_bytes_per_sec} ETA {eta}")
+            .unwrap()
+            .progress_chars("=> "));
+        total.set_message(format!("Total ({} packages)", count));
+
+        DownloadProgress { bars, total }
+    }
+
+    /// Adds the bar of a single download, above the total
+    pub fn add(&self, name: &String) -> ProgressBar {
+        let bar = self.bars.insert_before(&self.total, ProgressBar::new(0));
+        bar.set_style(ProgressStyle::with_template(" {msg:24!} [{bar:30}] {bytes}/{total_bytes} {binary_bytes_per_sec}")
+            .unwrap()
+            .progress_chars("=> "));
+        bar.set_message(name.clone());
+
+        bar
+    }
+
+    /// Adds bytes to the size of the run
+    pub fn add_length(&self, bytes: u64) {
+        self.total.inc_length(bytes);
+    }
+
+    /// Counts downloaded bytes towards the run
+    pub fn inc(&self, bytes: u64) {
+        self.total.inc(bytes);
+    }
+
+    /// Prints a message above the bars, or just prints it when they are hidden
+    pub fn message(&self, level: Level, line: String) {
+        if self.bars.is_hidden() {
+            message(level, line);
+        } else {
+            self.bars.println(format_message(level, &line, colour_enabled())).expect("Failed to print progress.");
+        }
+    }
+
+    pub fn finish(&self) {
+        self.total.finish();
+    }
+}
+
+/// Returns a bar for the entries extracted from a package, hidden when progress bars are disabled
+pub fn extract_progress(package: &String, entries: u64) -> ProgressBar {
+    if !progress_enabled() {
+        return ProgressBar::hidden();
+    }
+
+    let bar = ProgressBar::with_draw_target(Some(entries), ProgressDrawTarget::stdout());
+    bar.set_style(ProgressStyle::with_template(" {spinner} Extracting {msg:24!} [{bar:30}] {pos}/{len}")
+        .unwrap()
+        .progress_chars("=> "));
+    bar.set_message(package.clone());
+    bar.enable_steady_tick(Duration::from_millis(100));
+
+    bar
+}
+
+/// Formats a unix timestamp as "YYYY-MM-DD HH:MM:SS" in UTC
+pub fn format_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+/// Converts a string separated by "," to a vec of strings 
+pub fn string_to_vec(vec: String) -> Vec<String> {
+    vec.split(",").map(|s| s.to_string()).collect()
+}
+
+/// Gets the root of the current [Context], without a trailing "/"
+pub fn get_root() -> String {
+    match Context::current().root {
+        Some(root) => root.to_string_lossy().trim_end_matches('/').to_string(),
+        None => "".to_string(),
+    }
+}
+
+/// Returns an absolute path inside the root, e.g. `/etc/ps4/ps4.db` -> `<root>/etc/ps4/ps4.db`
+pub fn root_path(path: &str) -> PathBuf {
+    let root = get_root();
+
+    Path::new(if root.is_empty() { "/" } else { &root }).join(path.trim_start_matches('/'))
+}
+
+/// Returns the path of a database in the root, `ps4` for installed packages or the name of a repo
+pub fn db_path(name: &str) -> PathBuf {
+    root_path(&format!("/etc/ps4/{}.db", name))
+}
+
+/// Returns the temp directory used to unpack a package in the root
+pub fn tmp_path(package: &str) -> PathBuf {
+    root_path(&format!("/tmp/ps4/{}", package))
+}
+
+/// Fails with a permission error unless running as root, it's up to the caller to require_root and try again
+pub fn require_root() -> Result<(), Ps4Error> {
+    // geteuid can't fail
+    if unsafe { libc::geteuid() } == 0 {
+        return Ok(());
+    }
+
+    Err(Ps4Error::Permission("This needs to be run as root.".to_string()))
+}
+
+/// Returns true if the [Context] asks for more output
+pub fn is_verbose() -> bool {
+    Context::current().verbose
+}
+
+/// How long connecting to a mirror may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A transfer slower than LOW_SPEED_LIMIT bytes per second for LOW_SPEED_TIME has stalled and is given up
+const LOW_SPEED_LIMIT: u32 = 100;
+const LOW_SPEED_TIME: Duration = Duration::from_secs(30);
+
+/// Default isahc get
+pub fn get(url: &String) -> Result<Response<Body>, isahc::Error> {
+    if is_verbose() {
+        info(format!("GET {}", url));
+    }
+
+    return Request::get(url)
+            .redirect_policy(RedirectPolicy::Follow)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .low_speed_timeout(LOW_SPEED_LIMIT, LOW_SPEED_TIME)
+            .body(())?
+            .send();
+}
+
+/// isahc get starting at a byte offset, used to resume partial downloads
+pub fn get_range(url: &String, from: u64) -> Result<Response<Body>, isahc::Error> {
+    if is_verbose() {
+        info(format!("GET {} (from byte {})", url, from));
+    }
+
+    return Request::get(url)
+            .redirect_policy(RedirectPolicy::Follow)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .low_speed_timeout(LOW_SPEED_LIMIT, LOW_SPEED_TIME)
+            .header("Range", format!("bytes={}-", from))
+            .body(())?
+            .send();
+}
+
+/// The kinds of questions ps4 asks, each can be answered ahead of time by its own policy.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    General,
+    Downgrade,
+    FileConflict,
+    Replace,
+    StaleLock
+}
+
+impl PromptKind {
+    /// The answer the [Context] gives to this kind of prompt, None if it has to be asked
+    fn policy(&self, context: &Context) -> Option<bool> {
+        match self {
+            PromptKind::General => None,
+            PromptKind::Downgrade => context.downgrade,
+            PromptKind::FileConflict => context.file_conflicts,
+            PromptKind::Replace => context.replace,
+            PromptKind::StaleLock => context.stale_lock,
+        }
+    }
+
+    /// Policy values meaning yes and no
+    fn answers(&self) -> (&'static str, &'static str) {
+        match self {
+            PromptKind::General => ("yes", "no"),
+            PromptKind::Downgrade => ("allow", "deny"),
+            PromptKind::FileConflict => ("overwrite", "abort"),
+            PromptKind::Replace => ("accept", "reject"),
+            PromptKind::StaleLock => ("break", "keep"),
+        }
+    }
+
+    /// Answer used with --noconfirm when no policy was given, anything destructive defaults to no
+    fn noconfirm_answer(&self) -> bool {
+        match self {
+            PromptKind::General => true,
+            PromptKind::Replace => true,
+            PromptKind::Downgrade => false,
+            PromptKind::FileConflict => false,
+            PromptKind::StaleLock => false,
+        }
+    }
+}
+
+/// Returns true if the [Context] answers prompts without asking
+pub fn is_noconfirm() -> bool {
+    Context::current().noconfirm
+}
+
+pub fn continue_prompt() -> Result<bool, Ps4Error> {
+    prompt(PromptKind::General)
+}
+
+/// Asks a yes/no question, unless a policy or --noconfirm already answers it
+pub fn prompt(kind: PromptKind) -> Result<bool, Ps4Error> {
+    if let Some(answer) = kind.policy(&Context::current()) {
+        let (yes, no) = kind.answers();
+        println!("Continue? [yes/no]: {} ({})", if answer { "yes" } else { "no" }, if answer { yes } else { no });
+        return Ok(answer);
+    }
+
+    if is_noconfirm() {
+        let answer = kind.noconfirm_answer();
+        println!("Continue? [yes/no]: {} (noconfirm)", if answer { "yes" } else { "no" });
+        return Ok(answer);
+    }
+
+    let mut input = String::new();
+
+    print!("Continue? [yes/no]: ");
+
+    io::stdout().flush()?;
+    io::stdin().read_line(&mut input)?;
+
+    if input.trim().to_lowercase() == "y" {
+        return Ok(true);
+    } else if input.trim().to_lowercase() == "yes" {
+        return Ok(true);
+    } else if input.trim().to_lowercase() == "No" {
+        return Ok(false);
+    } else  {
+        return  Ok(false);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+/**************************************************************************/
+/* atom.rs                                                                */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt;
+use version_compare::{compare_to, Cmp};
+
+/// A package reference with an optional version constraint, e.g. `glibc>=2.38` or `python=3.12`.
+///
+/// Used for depends, conflicts, provides and replaces entries.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct PackageAtom {
+    pub name: String,
+    pub constraint: Option<(Cmp, String)>
+}
+
+impl PackageAtom {
+    /// Parse an atom, a bare name has no constraint
+    pub fn parse(atom: &str) -> PackageAtom {
+        let atom = atom.trim();
+
+        let op_start = atom.find(|c| c == '<' || c == '>' || c == '=');
+
+        if op_start.is_none() {
+            return PackageAtom { name: atom.to_string(), constraint: None };
+        }
+
+        let (name, rest) = atom.split_at(op_start.unwrap());
+
+        let (op, version) = if let Some(v) = rest.strip_prefix(">=") {
+            (Cmp::Ge, v)
+        } else if let Some(v) = rest.strip_prefix("<=") {
+            (Cmp::Le, v)
+        } else if let Some(v) = rest.strip_prefix("==") {
+            (Cmp::Eq, v)
+        } else if let Some(v) = rest.strip_prefix('>') {
+            (Cmp::Gt, v)
+        } else if let Some(v) = rest.strip_prefix('<') {
+            (Cmp::Lt, v)
+        } else {
+            (Cmp::Eq, &rest[1..])
+        };
+
+        PackageAtom {
+            name: name.trim().to_string(),
+            constraint: Some((op, version.trim().to_string()))
+        }
+    }
+
+    /// Returns true if the given version satisfies this atom's constraint, a constraint without a version never does
+    pub fn matches_version(&self, version: &str) -> bool {
+        match &self.constraint {
+            None => true,
+            Some((_, wanted)) if wanted.is_empty() => false,
+            Some((op, wanted)) => compare_to(version, wanted, *op).unwrap_or(false)
+        }
+    }
+
+    /// Returns true if a package with the given name, version and provides list satisfies this atom
+    pub fn satisfied_by(&self, name: &str, version: &str, provides: &str) -> bool {
+        if name == self.name && self.matches_version(version) {
+            return true;
+        }
+
+        for provided in parse_atoms(provides) {
+            if provided.name != self.name {
+                continue;
+            }
+
+            match (&self.constraint, &provided.constraint) {
+                // An unversioned requirement is met by any provider
+                (None, _) => return true,
+                // A versioned provide is checked like a real package version
+                (Some(_), Some((Cmp::Eq, provided_version))) => {
+                    if self.matches_version(provided_version) {
+                        return true;
+                    }
+                },
+                // An unversioned provide can't satisfy a versioned requirement
+                _ => {}
+            }
+        }
+
+        return false;
+    }
+}
+
+impl fmt::Display for PackageAtom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.constraint {
+            None => write!(f, "{}", self.name),
+            Some((Cmp::Eq, version)) => write!(f, "{}={}", self.name, version),
+            Some((op, version)) => write!(f, "{}{}{}", self.name, op.sign(), version)
+        }
+    }
+}
+
+/// Parse a "," separated list of atoms, skipping empty entries
+pub fn parse_atoms(list: &str) -> Vec<PackageAtom> {
+    list.split(",")
+        .filter(|s| !s.trim().is_empty())
+        .map(PackageAtom::parse)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_operators_without_spaces() {
+        assert_eq!(PackageAtom::parse("glibc>=2.38").constraint, Some((Cmp::Ge, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc<=2.38").constraint, Some((Cmp::Le, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc>2.38").constraint, Some((Cmp::Gt, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc<2.38").constraint, Some((Cmp::Lt, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc=2.38").constraint, Some((Cmp::Eq, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc==2.38").constraint, Some((Cmp::Eq, "2.38".to_string())));
+        assert_eq!(PackageAtom::parse("glibc>=2.38").name, "glibc");
+    }
+
+    #[test]
+    fn parses_bare_names_and_spaces() {
+        assert_eq!(PackageAtom::parse(" glibc "), PackageAtom { name: "glibc".to_string(), constraint: None });
+        assert_eq!(PackageAtom::parse("glibc >= 2.38"), PackageAtom { name: "glibc".to_string(), constraint: Some((Cmp::Ge, "2.38".to_string())) });
+    }
+
+    #[test]
+    fn malformed_atoms_match_no_version() {
+        let missing_version = PackageAtom::parse("glibc>=");
+        assert_eq!(missing_version.name, "glibc");
+        assert!(!missing_version.matches_version("2.38"));
+
+        let missing_name = PackageAtom::parse("=2.38");
+        assert_eq!(missing_name.name, "");
+        assert!(!missing_name.satisfied_by("glibc", "2.38", ""));
+    }
+
+    #[test]
+    fn matches_versions_against_the_constraint() {
+        let atom = PackageAtom::parse("glibc>=2.38");
+        assert!(atom.matches_version("2.38"));
+        assert!(atom.matches_version("2.39.1"));
+        assert!(!atom.matches_version("2.37"));
+
+        assert!(PackageAtom::parse("glibc<2.38").matches_version("2.9"));
+        assert!(!PackageAtom::parse("glibc=2.38").matches_version("2.38.1"));
+        assert!(PackageAtom::parse("glibc").matches_version("anything"));
+    }
+
+    #[test]
+    fn versioned_provides_satisfy_versioned_atoms() {
+        let atom = PackageAtom::parse("sh>=5");
+        assert!(atom.satisfied_by("bash", "5.2", "sh=5.2"));
+        assert!(!atom.satisfied_by("bash", "5.2", "sh"));
+        assert!(PackageAtom::parse("sh").satisfied_by("bash", "5.2", "sh"));
+    }
+
+    #[test]
+    fn parses_atom_lists() {
+        let atoms = parse_atoms("glibc>=2.38, ,zlib,,python=3.12");
+        let names: Vec<&str> = atoms.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["glibc", "zlib", "python"]);
+        assert_eq!(atoms[2].constraint, Some((Cmp::Eq, "3.12".to_string())));
+        assert!(parse_atoms("").is_empty());
+    }
+
+    #[test]
+    fn displays_atoms_as_parsed() {
+        assert_eq!(PackageAtom::parse("glibc>=2.38").to_string(), "glibc>=2.38");
+        assert_eq!(PackageAtom::parse("python==3.12").to_string(), "python=3.12");
+        assert_eq!(PackageAtom::parse("zlib").to_string(), "zlib");
+    }
+}
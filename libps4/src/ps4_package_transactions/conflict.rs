@@ -22,9 +22,11 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use std::path::Path;
-use crate::ps4::database::ps4dbmain::get_conflicts;
-use crate::ps4::database::ps4db::InstalledPS4Packages;
+use crate::database::ps4dbmain::get_conflicts;
+use crate::database::ps4db::InstalledPS4Packages;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_progess_bar::root_path;
+use crate::ps4error::Ps4Error;
 
 pub struct ConflictingFiles {
     pub is_conflict: bool,
@@ -36,30 +38,33 @@ pub struct ConflictingPackages {
     pub packages: Vec<InstalledPS4Packages>,
 }
 
-pub fn run_conflict_check(files: &Vec<String>, is_installed: bool, root: String) -> ConflictingFiles {
+/// Returns the files that already exist in the root, files are given as absolute paths inside the root
+pub fn run_conflict_check(files: &Vec<String>, is_installed: bool) -> ConflictingFiles {
     let mut conflicting_struct = ConflictingFiles {
         is_conflict: false,
         files: vec![]
     };
 
     for i in files {
-        if !is_installed && Path::new(format!("{}{}", &root, &i).as_str()).exists() {
+        let path = root_path(i);
+
+        if !is_installed && path.exists() {
             conflicting_struct.is_conflict = true;
-            conflicting_struct.files.push(format!("{}{}", root.clone(), i.clone()));
+            conflicting_struct.files.push(path.to_string_lossy().to_string());
         }
     }
 
     return conflicting_struct;
 }
 
-pub fn run_conflict_package_check(package: &String) -> ConflictingPackages {
+pub fn run_conflict_package_check(package: &PS4Package) -> Result<ConflictingPackages, Ps4Error> {
     let mut conflicting_struct = ConflictingPackages {
         is_conflict: false,
         packages: vec![]
     };
 
-    for i in get_conflicts(package) {
-        if &i.name == package {
+    for i in get_conflicts(&package.name, &package.version)? {
+        if i.name == package.name {
             // Whoops we found a conflict with ourselves, lets skip this one
             continue;
         }
@@ -68,5 +73,5 @@ pub fn run_conflict_package_check(package: &String) -> ConflictingPackages {
         conflicting_struct.packages.push(i);
     }
 
-    return conflicting_struct;
+    Ok(conflicting_struct)
 }
@@ -0,0 +1,401 @@
+/**************************************************************************/
+/* dependencies.rs                                                        */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::database::ps4dbmain::get_all_installed;
+use crate::database::ps4db::{Hold, InstalledPS4Packages};
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_transactions::atom::{parse_atoms, PackageAtom};
+use crate::ps4_package_transactions::conflict::run_conflict_package_check;
+use crate::ps4_package_transactions::selection::{choose_candidates, get_hold, Choice};
+use crate::ps4error::Ps4Error;
+
+/// A consistent set of packages to install.
+pub struct Resolution {
+    /// Packages to install and the repo each one comes from
+    pub install: Vec<(PS4Package, String)>,
+    /// Installed packages that have to be removed, with the name of the package replacing them
+    pub replaces: Vec<(String, InstalledPS4Packages)>
+}
+
+/// Why no consistent install set could be found.
+pub struct ResolveError {
+    pub reasons: Vec<String>
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "No consistent set of packages could be found:")?;
+        for i in &self.reasons {
+            writeln!(f, "\t{}", i)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ResolveError> for Ps4Error {
+    fn from(e: ResolveError) -> Ps4Error {
+        Ps4Error::Dependency(e.to_string().trim_end().to_string())
+    }
+}
+
+/// Something the solver still has to satisfy
+#[derive(Clone)]
+struct Goal {
+    atom: PackageAtom,
+    required_by: Option<String>,
+    /// Explicitly requested goals are always installed from a repo, even when already installed
+    explicit: bool
+}
+
+impl Goal {
+    fn describe(&self) -> String {
+        match &self.required_by {
+            Some(parent) => format!("{} (required by {})", self.atom, parent),
+            None => format!("{} (requested)", self.atom)
+        }
+    }
+}
+
+struct Solver {
+    /// Resolve as if nothing was installed, the root's database isn't read at all
+    ignore_installed: bool,
+    installed: Vec<InstalledPS4Packages>,
+    candidates: HashMap<String, Choice>,
+    /// Holds on installed packages
+    holds: Vec<Hold>,
+    reasons: Vec<String>
+}
+
+impl Solver {
+    fn fail(&mut self, reason: String) {
+        if !self.reasons.contains(&reason) {
+            self.reasons.push(reason);
+        }
+    }
+
+    /// Candidates for a name best first, respecting pins and repo priorities
+    fn candidates_for(&mut self, name: &String) -> Result<&Choice, Ps4Error> {
+        if !self.candidates.contains_key(name) {
+            self.candidates.insert(name.clone(), choose_candidates(name)?);
+        }
+
+        Ok(&self.candidates[name])
+    }
+
+    /// Installed packages that will still be on the system alongside the selected set
+    fn kept_installed(&self, selected: &Vec<(PS4Package, String)>) -> Vec<InstalledPS4Packages> {
+        self.installed.iter()
+            .filter(|i| !selected.iter().any(|s| s.0.name == i.name))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a reason if candidate can't be installed next to the already selected and installed packages
+    fn check_candidate(&self, candidate: &PS4Package, selected: &Vec<(PS4Package, String)>) -> Result<Option<String>, Ps4Error> {
+        for (other, _) in selected {
+            if other.name == candidate.name {
+                return Ok(Some(format!("{} {} can't be installed as {} {} is already selected",
+                                    candidate.name, candidate.version, other.name, other.version)));
+            }
+
+            if parse_atoms(&candidate.conflicts).iter().any(|a| a.satisfied_by(&other.name, &other.version, &other.provides))
+                || parse_atoms(&other.conflicts).iter().any(|a| a.satisfied_by(&candidate.name, &candidate.version, &candidate.provides)) {
+                return Ok(Some(format!("{} {} conflicts with {} {}", candidate.name, candidate.version, other.name, other.version)));
+            }
+        }
+
+        if let Some(hold) = self.holds.iter().find(|h| h.name == candidate.name) {
+            if !hold.allows(&candidate.version) {
+                return Ok(Some(format!("{} {} is not allowed by the hold {} (use --ignore-holds to override)", candidate.name, candidate.version, hold)));
+            }
+        }
+
+        let kept = self.kept_installed(selected);
+
+        for installed in &kept {
+            if installed.name == candidate.name {
+                continue;
+            }
+
+            if replaces(candidate, installed) {
+                if let Some(hold) = self.holds.iter().find(|h| h.name == installed.name) {
+                    return Ok(Some(format!("{} would replace {}, which is held by {} (use --ignore-holds to override)", candidate.name, installed.name, hold)));
+                }
+
+                continue;
+            }
+
+            let provides = installed.provides.join(",");
+
+            if parse_atoms(&candidate.conflicts).iter().any(|a| a.satisfied_by(&installed.name, &installed.version, &provides)) {
+                return Ok(Some(format!("{} {} conflicts with installed {} {}", candidate.name, candidate.version, installed.name, installed.version)));
+            }
+
+            // Installing a new version must not break what already depends on it
+            for dep in installed.dependencies.iter().map(|d| PackageAtom::parse(d)) {
+                if dep.name == candidate.name && !dep.matches_version(&candidate.version) {
+                    return Ok(Some(format!("{} {} would break installed {} {} which requires {}",
+                                        candidate.name, candidate.version, installed.name, installed.version, dep)));
+                }
+            }
+        }
+
+        if !self.ignore_installed {
+            for installed in run_conflict_package_check(candidate)?.packages {
+                if kept.iter().any(|k| k.name == installed.name) && !replaces(candidate, &installed) {
+                    return Ok(Some(format!("installed {} {} conflicts with {} {}", installed.name, installed.version, candidate.name, candidate.version)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns None when no solution exists, errors only when the databases can't be read
+    fn solve(&mut self, mut goals: Vec<Goal>, selected: Vec<(PS4Package, String)>) -> Result<Option<Vec<(PS4Package, String)>>, Ps4Error> {
+        if goals.is_empty() {
+            return Ok(Some(selected));
+        }
+
+        let goal = goals.remove(0);
+
+        // Already satisfied by something we picked
+        if selected.iter().any(|s| goal.atom.satisfied_by(&s.0.name, &s.0.version, &s.0.provides)) {
+            return self.solve(goals, selected);
+        }
+
+        // Already satisfied by the system
+        if !goal.explicit && self.kept_installed(&selected).iter()
+            .any(|i| goal.atom.satisfied_by(&i.name, &i.version, &i.provides.join(","))) {
+            return self.solve(goals, selected);
+        }
+
+        let choice = self.candidates_for(&goal.atom.name)?;
+        let all_candidates = choice.candidates.clone();
+        let choice_reason = choice.reason.clone();
+        let candidates: Vec<(PS4Package, String)> = all_candidates.iter()
+            .filter(|c| goal.atom.satisfied_by(&c.0.name, &c.0.version, &c.0.provides))
+            .cloned()
+            .collect();
+
+        if candidates.is_empty() {
+            if all_candidates.is_empty() {
+                self.fail(format!("nothing provides {}, {}", goal.describe(), choice_reason));
+            } else {
+                let available: Vec<String> = all_candidates.iter()
+                    .map(|c| format!("{} {} in {}", c.0.name, c.0.version, c.1))
+                    .collect();
+                self.fail(format!("nothing satisfies {}, available: {}", goal.describe(), available.join(", ")));
+            }
+
+            return Ok(None);
+        }
+
+        for candidate in candidates {
+            if let Some(reason) = self.check_candidate(&candidate.0, &selected)? {
+                self.fail(format!("{}: {}", goal.describe(), reason));
+                continue;
+            }
+
+            let mut next_goals = goals.clone();
+            for dep in parse_atoms(&candidate.0.depends) {
+                next_goals.push(Goal { atom: dep, required_by: Some(candidate.0.name.clone()), explicit: false });
+            }
+
+            let mut next_selected = selected.clone();
+            next_selected.push(candidate);
+
+            if let Some(solution) = self.solve(next_goals, next_selected)? {
+                return Ok(Some(solution));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Returns true if package declares that it replaces (or provides) an installed package
+fn replaces(package: &PS4Package, installed: &InstalledPS4Packages) -> bool {
+    parse_atoms(&package.replaces).iter()
+        .chain(parse_atoms(&package.provides).iter())
+        .any(|a| a.name == installed.name && a.matches_version(&installed.version))
+}
+
+/// Find one consistent set of packages that satisfies every requested atom, including dependencies.
+///
+/// Requested atoms are always installed from a repo, dependencies only when nothing installed satisfies them.
+/// With ignore_installed the full dependency closure is returned, as if nothing was installed, and the
+/// root's database isn't opened so it doesn't have to exist.
+/// Held installed packages are only changed to versions their hold allows, unless ignore_holds is set.
+pub fn resolve(requested: &Vec<String>, ignore_installed: bool, ignore_holds: bool) -> Result<Resolution, Ps4Error> {
+    let mut solver = Solver {
+        ignore_installed,
+        installed: if ignore_installed { vec![] } else { get_all_installed()? },
+        candidates: HashMap::new(),
+        holds: vec![],
+        reasons: vec![]
+    };
+
+    if !ignore_holds && !ignore_installed {
+        for i in &solver.installed {
+            if let Some(hold) = get_hold(&i.name)? {
+                solver.holds.push(hold);
+            }
+        }
+    }
+
+    let goals: Vec<Goal> = requested.iter()
+        .map(|r| Goal { atom: PackageAtom::parse(r), required_by: None, explicit: true })
+        .collect();
+
+    let install = match solver.solve(goals, vec![])? {
+        Some(install) => install,
+        None => return Err(ResolveError { reasons: solver.reasons }.into())
+    };
+
+    let mut replaced: Vec<(String, InstalledPS4Packages)> = vec![];
+
+    if ignore_installed {
+        return Ok(Resolution { install, replaces: replaced });
+    }
+
+    for (package, _) in &install {
+        let conflicting = run_conflict_package_check(package)?.packages;
+
+        for installed in solver.kept_installed(&install) {
+            if !replaces(package, &installed) {
+                continue;
+            }
+
+            // A provider only replaces an installed package it can't live next to
+            let declared = parse_atoms(&package.replaces).iter().any(|a| a.name == installed.name);
+            let conflicts = conflicting.iter().any(|c| c.name == installed.name)
+                || parse_atoms(&package.conflicts).iter()
+                    .any(|a| a.satisfied_by(&installed.name, &installed.version, &installed.provides.join(",")));
+
+            if declared || conflicts {
+                replaced.push((package.name.clone(), installed));
+            }
+        }
+    }
+
+    Ok(Resolution { install, replaces: replaced })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, depends: &str, conflicts: &str) -> (PS4Package, String) {
+        (PS4Package {
+            name: name.to_string(),
+            version: version.to_string(),
+            upstream: 1,
+            description: String::new(),
+            groups: String::new(),
+            url: String::new(),
+            license: String::new(),
+            depends: depends.to_string(),
+            optional_depends: String::new(),
+            provides: String::new(),
+            conflicts: conflicts.to_string(),
+            replaces: String::new(),
+            sha512sum: String::new()
+        }, "core".to_string())
+    }
+
+    /// A solver over the given repo candidates, nothing installed and no database read
+    fn solver(packages: Vec<(PS4Package, String)>) -> Solver {
+        let mut candidates: HashMap<String, Choice> = HashMap::new();
+
+        for package in packages {
+            candidates.entry(package.0.name.clone())
+                .or_insert_with(|| Choice { candidates: vec![], reason: "from core".to_string() })
+                .candidates.push(package);
+        }
+
+        Solver { ignore_installed: true, installed: vec![], candidates, holds: vec![], reasons: vec![] }
+    }
+
+    fn goals(requested: &[&str]) -> Vec<Goal> {
+        requested.iter()
+            .map(|r| Goal { atom: PackageAtom::parse(r), required_by: None, explicit: true })
+            .collect()
+    }
+
+    fn versions(solution: &Vec<(PS4Package, String)>) -> Vec<String> {
+        solution.iter().map(|p| format!("{} {}", p.0.name, p.0.version)).collect()
+    }
+
+    #[test]
+    fn skips_a_candidate_conflicting_with_the_selection() {
+        let mut solver = solver(vec![
+            package("b", "1.0", "", ""),
+            package("a", "2.0", "", "b"),
+            package("a", "1.0", "", "")
+        ]);
+
+        let solution = solver.solve(goals(&["b", "a"]), vec![]).unwrap().unwrap();
+
+        assert_eq!(versions(&solution), vec!["b 1.0", "a 1.0"]);
+        assert_eq!(solver.reasons, vec!["a (requested): a 2.0 conflicts with b 1.0"]);
+    }
+
+    #[test]
+    fn backtracks_when_a_dependency_of_the_first_candidate_fails() {
+        let mut solver = solver(vec![
+            package("a", "2.0", "x>=2", ""),
+            package("a", "1.0", "x", ""),
+            package("x", "1.0", "", "")
+        ]);
+
+        let solution = solver.solve(goals(&["a"]), vec![]).unwrap().unwrap();
+
+        assert_eq!(versions(&solution), vec!["a 1.0", "x 1.0"]);
+    }
+
+    #[test]
+    fn explains_an_unsatisfiable_version() {
+        let mut solver = solver(vec![
+            package("a", "1.0", "x>=2", ""),
+            package("x", "1.0", "", "")
+        ]);
+
+        assert!(solver.solve(goals(&["a"]), vec![]).unwrap().is_none());
+        assert_eq!(solver.reasons, vec!["nothing satisfies x>=2 (required by a), available: x 1.0 in core"]);
+    }
+
+    #[test]
+    fn explains_a_missing_dependency() {
+        let mut solver = solver(vec![package("a", "1.0", "missing", "")]);
+        solver.candidates.insert("missing".to_string(), Choice { candidates: vec![], reason: "no repo has it".to_string() });
+
+        assert!(solver.solve(goals(&["a"]), vec![]).unwrap().is_none());
+
+        let error: Ps4Error = ResolveError { reasons: solver.reasons }.into();
+        assert_eq!(error.to_string(), "No consistent set of packages could be found:\n\tnothing provides missing (required by a), no repo has it");
+    }
+}
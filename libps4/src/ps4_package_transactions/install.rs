@@ -0,0 +1,269 @@
+/**************************************************************************/
+/* install.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+use std::fs::File;
+use flate2::read::GzDecoder;
+use tar::{Archive, EntryType};
+use version_compare::Version;
+use crate::database::ps4dbmain::{add_package_to_installed, get_installed_package, return_owned_files};
+use crate::database::ps4db::{FileType, InstalledFile, Source};
+use crate::ps4error::Ps4Error;
+use crate::ps4_lock_package::{critical, workspace};
+use crate::ps4_package_progess_bar::{extract_progress, get_root, prompt, root_path, string_to_vec, PromptKind};
+use crate::packaging::ps4_packageing_main::{decode_pkg_file, decompress_gz};
+use crate::packaging::ps4_packageing_setup::{PS4NewPackage, PS4Package};
+use crate::ps4_package_transactions::conflict::run_conflict_check;
+use crate::ps4_package_transactions::integrity::file_metadata;
+use crate::ps4_package_transactions::staging::{normalize_entry_path, StagedInstall};
+use crate::ps4output::{info, warning};
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct InstallTransaction {
+    pub package: PS4Package,
+    pub source: Source,
+}
+
+/// The type recorded for an entry of a data archive
+fn file_type(entry_type: EntryType) -> FileType {
+    match entry_type {
+        EntryType::Directory => FileType::Dir,
+        EntryType::Symlink => FileType::Symlink,
+        EntryType::Link => FileType::Hardlink,
+        _ => FileType::File
+    }
+}
+
+/// Lists the entries of a data archive as they will be installed, along with how many entries it has.
+///
+/// The entry's own path is used rather than the header's, GNU long names and PAX records hold paths over 100 bytes.
+fn archive_files(mut data_tar: Archive<GzDecoder<File>>) -> Result<(Vec<InstalledFile>, u64), Ps4Error> {
+    let mut files: Vec<InstalledFile> = vec![];
+    let mut entries: u64 = 0;
+
+    for x in data_tar.entries()?.filter_map(|e| e.ok()) {
+        entries += 1;
+
+        if let Some(path) = x.path().ok().and_then(|p| normalize_entry_path(&p)) {
+            files.push(InstalledFile {
+                path: format!("/{}", path.to_string_lossy()),
+                file_type: file_type(x.header().entry_type()),
+                metadata: None
+            });
+        }
+    }
+
+    Ok((files, entries))
+}
+
+pub fn run_install(install: InstallTransaction, file: File) -> Result<(), Ps4Error> {
+    let mut package_tar = decompress_gz(file);
+    let workspace = workspace(&install.package.name);
+
+    package_tar.unpack(&workspace)?;
+
+    let package = decode_pkg_file(fs::File::open(workspace.join("PS4PKG"))?)?;
+
+    // Check if package is already installed
+    let installed_pkg = get_installed_package(&package.name)?;
+    if let Some(installed_pkg) = &installed_pkg {
+        // Check if this is a downgrade
+        if Version::from(&package.version) < Version::from(&installed_pkg.version) {
+            // Ask the user if they'd like to still install the specified package
+            warning(format!("This will result in a downgrade as {} v{} is already installed!", &package.name, &installed_pkg.version));
+
+            let s = prompt(PromptKind::Downgrade)?;
+
+            if !s {
+                info("Abandoning install!");
+
+                return Err(Ps4Error::Aborted);
+            }
+        } else if (Version::from(&package.version) == Version::from(&installed_pkg.version)) && (&package.upstream == &installed_pkg.upstream) {
+            warning(format!("{} is already installed, reinstalling...", &package.name));
+        }
+    }
+
+    // Calculate files to be installed
+    let (mut files, entries) = archive_files(decompress_gz(fs::File::open(workspace.join("data.tar.gz"))?))?;
+
+    let paths: Vec<String> = files.iter()
+        .filter(|f| f.file_type != FileType::Dir)
+        .map(|f| f.path.clone())
+        .collect();
+
+    let conflicting = run_conflict_check(&paths, installed_pkg.is_some());
+
+    if conflicting.is_conflict {
+        warning("Package files already exist on the file system!");
+
+        for i in &conflicting.files {
+            println!("\t{}", i);
+        }
+
+        let s = prompt(PromptKind::FileConflict)?;
+
+        if !s {
+            info("Abandoning install!");
+
+            return Err(Ps4Error::Aborted);
+        } else {
+            info("Continuing install, existing files will be replaced!");
+        }
+    }
+
+    let previous_files = return_owned_files(&package.name)?;
+
+    // Open data tar for extraction
+    let data_tar = decompress_gz(fs::File::open(workspace.join("data.tar.gz"))?);
+
+    // Once extraction started the package is either installed completely or rolled back, even when interrupted
+    critical(|| {
+        // Extract next to the targets first, only move files into place once everything extracted
+        let mut staged = StagedInstall::new(&get_root());
+
+        let progress = extract_progress(&install.package.name, entries);
+
+        let result = staged.stage(data_tar, &progress)
+            .and_then(|_| staged.replace())
+            .map_err(Ps4Error::from)
+            .and_then(|_| {
+                // Directories created for the entries belong to the package too, as do those of the version it replaces
+                let previous_dirs = previous_files.iter()
+                    .filter(|f| f.file_type == FileType::Dir && root_path(&f.path).is_dir())
+                    .map(|f| f.path.clone());
+
+                for dir in staged.created_dirs().into_iter().chain(previous_dirs) {
+                    if !files.iter().any(|f| f.path == dir) {
+                        files.push(InstalledFile { path: dir, file_type: FileType::Dir, metadata: None });
+                    }
+                }
+
+                // Record what everything looks like once in place, `ps4 verify` compares against it
+                for file in files.iter_mut() {
+                    let metadata = file_metadata(&root_path(&file.path))
+                        .map_err(|e| Ps4Error::from(e).context(format!("Could not read {}", file.path)))?;
+
+                    file.metadata = Some(metadata);
+                }
+
+                //Add package to database
+                add_package_to_installed(PS4NewPackage {
+                    name: install.package.name.clone(),
+                    groups: install.package.groups,
+                    version: install.package.version.clone(),
+                    upstream: install.package.upstream,
+                    installed_files: files,
+                    provides: string_to_vec(install.package.provides),
+                    conflicts: string_to_vec(install.package.conflicts),
+                    dependencies: string_to_vec(install.package.depends),
+                }, install.source)
+            });
+
+        progress.finish_and_clear();
+
+        if let Err(e) = result {
+            staged.rollback();
+
+            return Err(e.context(format!("Failed to install {}, all changes were rolled back", install.package.name)));
+        }
+
+        staged.commit();
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use indicatif::ProgressBar;
+
+    use super::*;
+
+    /// Writes a data archive holding a single file at `path`
+    fn data_archive(dir: &PathBuf, path: &str, content: &[u8]) -> PathBuf {
+        let archive = dir.join("data.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(File::create(&archive).unwrap(), Compression::default()));
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, path, content).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        archive
+    }
+
+    #[test]
+    fn installs_paths_longer_than_a_tar_header() {
+        let dir = std::env::temp_dir().join(format!("ps4-install-test-{}", std::process::id()));
+        let root = dir.join("root");
+        fs::create_dir_all(&root).unwrap();
+
+        let long = format!("usr/share/{}/{}", "d".repeat(120), "f".repeat(40));
+        let archive = data_archive(&dir, &long, b"hello");
+
+        let (files, entries) = archive_files(decompress_gz(File::open(&archive).unwrap())).unwrap();
+        assert_eq!(entries, 1);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, format!("/{}", long));
+
+        let mut staged = StagedInstall::new(&root.to_string_lossy());
+        staged.stage(decompress_gz(File::open(&archive).unwrap()), &ProgressBar::hidden()).unwrap();
+        staged.replace().unwrap();
+        staged.commit();
+
+        // Recording the metadata is what failed the install when the path was truncated
+        let metadata = file_metadata(&root.join(&long)).unwrap();
+        assert_eq!(metadata.size, 5);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_stage_through_a_symlink_out_of_the_root() {
+        let dir = std::env::temp_dir().join(format!("ps4-escape-test-{}", std::process::id()));
+        let root = dir.join("root");
+        let outside = dir.join("outside");
+        fs::create_dir_all(root.join("usr")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("usr/evil")).unwrap();
+
+        let archive = data_archive(&dir, "usr/evil/pwned", b"pwned");
+
+        let mut staged = StagedInstall::new(&root.to_string_lossy());
+        let result = staged.stage(decompress_gz(File::open(&archive).unwrap()), &ProgressBar::hidden());
+        staged.rollback();
+
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+        assert_eq!(fs::read_dir(&outside).unwrap().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+/**************************************************************************/
+/* integrity.rs                                                           */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use hex::ToHex;
+use ring::digest::{digest, SHA256};
+
+use crate::database::ps4db::{FileMetadata, FileType, InstalledFile};
+use crate::ps4cache::digest_file;
+use crate::ps4_package_progess_bar::root_path;
+
+/// How an installed file differs from what was recorded when it was installed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum FileProblem {
+    Missing,
+    /// The content, link target or kind of entry changed
+    Modified(String),
+    /// The mode or owner changed
+    Permissions(String),
+    /// The file exists but couldn't be read to compare it
+    Unreadable(String)
+}
+
+impl fmt::Display for FileProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileProblem::Missing => write!(f, "missing"),
+            FileProblem::Modified(detail) => write!(f, "modified ({})", detail),
+            FileProblem::Permissions(detail) => write!(f, "permissions ({})", detail),
+            FileProblem::Unreadable(detail) => write!(f, "unreadable ({})", detail),
+        }
+    }
+}
+
+/// The kind of entry found on disk, hardlinks can't be told apart from files once installed
+fn found_type(metadata: &fs::Metadata) -> FileType {
+    if metadata.file_type().is_symlink() {
+        FileType::Symlink
+    } else if metadata.is_dir() {
+        FileType::Dir
+    } else {
+        FileType::File
+    }
+}
+
+/// Reads the metadata recorded for a file, without following symlinks
+pub fn file_metadata(path: &Path) -> io::Result<FileMetadata> {
+    let metadata = path.symlink_metadata()?;
+
+    let sha256 = match found_type(&metadata) {
+        FileType::Symlink => Some(digest(&SHA256, fs::read_link(path)?.as_os_str().as_bytes()).as_ref().encode_hex::<String>()),
+        FileType::Dir => None,
+        _ => Some(digest_file(path, &SHA256)?)
+    };
+
+    Ok(FileMetadata {
+        mode: metadata.mode() & 0o7777,
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        size: metadata.len(),
+        mtime: metadata.mtime(),
+        sha256
+    })
+}
+
+/// Compares an installed file in the root with what was recorded for it.
+///
+/// The mtime isn't compared, directories change it whenever an entry is added and touching a file isn't tampering with it.
+/// Files recorded without metadata are only checked for presence.
+pub fn check_file(file: &InstalledFile) -> Vec<FileProblem> {
+    let path = root_path(&file.path);
+
+    let Ok(found) = path.symlink_metadata() else {
+        return vec![FileProblem::Missing];
+    };
+
+    let Some(expected) = &file.metadata else {
+        return vec![];
+    };
+
+    let recorded_type = if file.file_type == FileType::Hardlink { FileType::File } else { file.file_type };
+
+    if found_type(&found) != recorded_type {
+        return vec![FileProblem::Modified(format!("was a {}, now a {}", recorded_type, found_type(&found)))];
+    }
+
+    let current = match file_metadata(&path) {
+        Ok(current) => current,
+        Err(e) => return vec![FileProblem::Unreadable(e.to_string())]
+    };
+
+    let mut problems: Vec<FileProblem> = vec![];
+
+    if current.sha256 != expected.sha256 {
+        problems.push(FileProblem::Modified(match recorded_type {
+            FileType::Symlink => "link target changed".to_string(),
+            _ if current.size != expected.size => format!("size {} -> {}", expected.size, current.size),
+            _ => "content changed".to_string()
+        }));
+    }
+
+    // A symlink's own mode and owner mean nothing on Linux
+    if recorded_type != FileType::Symlink {
+        if current.mode != expected.mode {
+            problems.push(FileProblem::Permissions(format!("mode {:04o} -> {:04o}", expected.mode, current.mode)));
+        }
+
+        if current.uid != expected.uid || current.gid != expected.gid {
+            problems.push(FileProblem::Permissions(format!("owner {}:{} -> {}:{}", expected.uid, expected.gid, current.uid, current.gid)));
+        }
+    }
+
+    problems
+}
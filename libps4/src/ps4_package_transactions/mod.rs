@@ -25,3 +25,7 @@
 pub mod install;
 pub mod conflict;
 pub mod dependencies;
+pub mod atom;
+pub mod staging;
+pub mod selection;
+pub mod integrity;
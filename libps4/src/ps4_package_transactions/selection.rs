@@ -0,0 +1,132 @@
+/**************************************************************************/
+/* selection.rs                                                           */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use version_compare::compare;
+
+use crate::database::ps4db::Hold;
+use crate::database::ps4dbmain::{get_candidates, get_holds, get_installed_package};
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_config::ps4_mirror_config_main::{get_enabled_repos, get_pinned_repo, is_held_in_config};
+use crate::ps4error::Ps4Error;
+
+/// The repo candidates for a name, best first, and why the first one is preferred.
+pub struct Choice {
+    pub candidates: Vec<(PS4Package, String)>,
+    pub reason: String
+}
+
+/// Orders two packages by version, then upstream
+pub fn compare_versions(a: &PS4Package, b: &PS4Package) -> Ordering {
+    compare_releases((&a.version, a.upstream), (&b.version, b.upstream))
+}
+
+/// Orders two version and upstream pairs like `compare_versions`, for installed packages
+pub fn compare_releases(a: (&str, i32), b: (&str, i32)) -> Ordering {
+    compare(a.0, b.0).ok()
+        .and_then(|c| c.ord())
+        .unwrap_or(Ordering::Equal)
+        .then(a.1.cmp(&b.1))
+}
+
+/// Rank the candidates for a name.
+///
+/// A pin limits the candidates to the pinned repo. Otherwise higher priority repos come first,
+/// then the package with the requested name before its providers, then the newest version.
+/// Anything still equal keeps the order of the config.
+pub fn rank_candidates(name: &String, candidates: Vec<(PS4Package, String)>) -> Choice {
+    let priorities: HashMap<String, i64> = get_enabled_repos().into_iter()
+        .map(|i| (i.name, i.priority))
+        .collect();
+    let priority = |repo: &String| *priorities.get(repo).unwrap_or(&0);
+
+    let pinned = get_pinned_repo(name);
+
+    let mut candidates: Vec<(PS4Package, String)> = match &pinned {
+        Some(repo) => candidates.into_iter().filter(|c| &c.1 == repo).collect(),
+        None => candidates
+    };
+
+    candidates.sort_by(|a, b| priority(&b.1).cmp(&priority(&a.1))
+        .then((&b.0.name == name).cmp(&(&a.0.name == name)))
+        .then(compare_versions(&b.0, &a.0)));
+
+    let reason = match (&pinned, candidates.first(), candidates.get(1)) {
+        (Some(repo), None, _) => format!("{} is pinned to {}, which doesn't have it", name, repo),
+        (None, None, _) => format!("no enabled repo has {}", name),
+        (Some(repo), Some(_), _) => format!("{} is pinned to {} in the config", name, repo),
+        (None, Some(best), None) => format!("{} is the only repo with it", best.1),
+        (None, Some(best), Some(next)) => {
+            if priority(&best.1) > priority(&next.1) {
+                format!("{} has the highest priority ({})", best.1, priority(&best.1))
+            } else if &best.0.name == name && &next.0.name != name {
+                format!("{} itself is preferred over packages providing it", name)
+            } else if compare_versions(&best.0, &next.0) == Ordering::Greater {
+                format!("newest version among repos with priority {}", priority(&best.1))
+            } else {
+                format!("{} comes first in the config", best.1)
+            }
+        }
+    };
+
+    Choice { candidates, reason }
+}
+
+/// Rank every package named or providing name across the enabled repos
+pub fn choose_candidates(name: &String) -> Result<Choice, Ps4Error> {
+    Ok(rank_candidates(name, get_candidates(name)?))
+}
+
+/// Rank only the packages with exactly this name, as used for upgrades and `ps4 info`
+pub fn choose_package(name: &String) -> Result<Choice, Ps4Error> {
+    let candidates = get_candidates(name)?.into_iter()
+        .filter(|c| &c.0.name == name)
+        .collect();
+
+    Ok(rank_candidates(name, candidates))
+}
+
+/// Returns the hold on a package, from `ps4 hold` or else from the config
+pub fn get_hold(name: &String) -> Result<Option<Hold>, Ps4Error> {
+    if let Some(hold) = get_holds()?.into_iter().find(|h| &h.name == name) {
+        return Ok(Some(hold));
+    }
+
+    if is_held_in_config(name) {
+        return Ok(Some(Hold { name: name.clone(), version: None }));
+    }
+
+    Ok(None)
+}
+
+/// Returns the hold on an installed package that doesn't allow changing it to version, for installs that bypass the resolver
+pub fn blocking_hold(name: &String, version: &str) -> Result<Option<Hold>, Ps4Error> {
+    if get_installed_package(name)?.is_none() {
+        return Ok(None);
+    }
+
+    Ok(get_hold(name)?.filter(|hold| !hold.allows(version)))
+}
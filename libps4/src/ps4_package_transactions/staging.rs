@@ -0,0 +1,259 @@
+/**************************************************************************/
+/* staging.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use indicatif::ProgressBar;
+use tar::{Archive, EntryType};
+
+/// A single change made to the file system, kept so it can be undone.
+enum Change {
+    /// A directory that didn't exist before the transaction
+    CreatedDir(PathBuf),
+    /// A new file extracted next to its target, waiting to be moved into place
+    Staged(PathBuf),
+    /// An existing file moved aside so the new one can take its place
+    BackedUp { target: PathBuf, backup: PathBuf },
+    /// A staged file moved onto its target
+    Replaced(PathBuf)
+}
+
+/// Stages a package's files next to their targets and moves them into place, undoing everything on failure.
+pub struct StagedInstall {
+    root: PathBuf,
+    /// (staging path, target path) for every non directory entry, in archive order
+    pending: Vec<(PathBuf, PathBuf)>,
+    changes: Vec<Change>
+}
+
+/// Returns `<parent>/.ps4-<tag>.<file name>` for a target path
+fn sibling(target: &Path, tag: &str) -> PathBuf {
+    let file_name = target.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+    target.with_file_name(format!(".ps4-{}.{}", tag, file_name))
+}
+
+/// Strips `./` and rejects paths that would escape the root
+pub fn normalize_entry_path(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {},
+            _ => return None
+        }
+    }
+
+    if normalized.as_os_str().is_empty() {
+        return None;
+    }
+
+    Some(normalized)
+}
+
+/// Fails unless path resolves inside the root once the symlinks that already exist are followed
+fn check_inside_root(root: &Path, path: &Path) -> io::Result<()> {
+    // Whatever is missing gets created as a real directory, only the existing part can lead elsewhere
+    let mut existing = path;
+    while existing.symlink_metadata().is_err() {
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => break
+        }
+    }
+
+    if !fs::canonicalize(existing)?.starts_with(root) {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied,
+                                  format!("{} is outside of the root {}", path.display(), root.display())));
+    }
+
+    Ok(())
+}
+
+impl StagedInstall {
+    pub fn new(root: &str) -> StagedInstall {
+        StagedInstall {
+            root: PathBuf::from(if root.is_empty() { "/" } else { root }),
+            pending: vec![],
+            changes: vec![]
+        }
+    }
+
+    /// Create every missing directory up to and including dir
+    fn ensure_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let mut missing: Vec<PathBuf> = vec![];
+        let mut current = Some(dir);
+
+        while let Some(path) = current {
+            if path.symlink_metadata().is_ok() {
+                break;
+            }
+            missing.push(path.to_path_buf());
+            current = path.parent();
+        }
+
+        for path in missing.into_iter().rev() {
+            fs::create_dir(&path)?;
+            self.changes.push(Change::CreatedDir(path));
+        }
+
+        Ok(())
+    }
+
+    /// Extract every entry of a data archive to a staging path next to its target.
+    ///
+    /// Nothing that already exists on the root is touched yet. Entries whose parent resolves outside the root,
+    /// through a symlink on the root, fail the whole stage.
+    pub fn stage(&mut self, mut data_tar: Archive<GzDecoder<File>>, progress: &ProgressBar) -> io::Result<()> {
+        let root = fs::canonicalize(&self.root)?;
+
+        // Hard links point at another entry of the same archive, which may only be staged so far
+        let mut staged_paths: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+        for entry in data_tar.entries()? {
+            let mut entry = entry?;
+
+            progress.inc(1);
+
+            let relative = match normalize_entry_path(&entry.path()?) {
+                Some(relative) => relative,
+                None => continue
+            };
+
+            let target = self.root.join(&relative);
+
+            if let Some(parent) = target.parent() {
+                check_inside_root(&root, parent)?;
+            }
+
+            if entry.header().entry_type() == EntryType::Directory {
+                if target.symlink_metadata().is_err() {
+                    if let Some(parent) = target.parent() {
+                        self.ensure_dir(parent)?;
+                    }
+                    entry.set_preserve_permissions(true);
+                    entry.unpack(&target)?;
+                    self.changes.push(Change::CreatedDir(target));
+                }
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                self.ensure_dir(parent)?;
+            }
+
+            let staging = sibling(&target, "new");
+
+            if staging.symlink_metadata().is_ok() {
+                fs::remove_file(&staging)?;
+            }
+
+            self.changes.push(Change::Staged(staging.clone()));
+
+            if entry.header().entry_type() == EntryType::Link {
+                let link_name = entry.link_name()?
+                    .and_then(|l| normalize_entry_path(&l))
+                    .ok_or(io::Error::new(io::ErrorKind::InvalidData, "Hard link without a valid target"))?;
+
+                let source = match staged_paths.get(&link_name) {
+                    Some(staged) => staged.clone(),
+                    None => {
+                        let source = self.root.join(&link_name);
+                        if let Some(parent) = source.parent() {
+                            check_inside_root(&root, parent)?;
+                        }
+                        source
+                    }
+                };
+                fs::hard_link(source, &staging)?;
+            } else {
+                entry.set_preserve_permissions(true);
+                entry.set_unpack_xattrs(true);
+                entry.unpack(&staging)?;
+            }
+
+            staged_paths.insert(relative, staging.clone());
+            self.pending.push((staging, target));
+        }
+
+        Ok(())
+    }
+
+    /// Move every staged file onto its target, backing up whatever was there
+    pub fn replace(&mut self) -> io::Result<()> {
+        for (staging, target) in self.pending.clone() {
+            if let Ok(metadata) = target.symlink_metadata() {
+                if metadata.is_dir() {
+                    return Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                                              format!("{} is a directory", target.display())));
+                }
+
+                let backup = sibling(&target, "old");
+                fs::rename(&target, &backup)?;
+                self.changes.push(Change::BackedUp { target: target.clone(), backup });
+            }
+
+            fs::rename(&staging, &target)?;
+            self.changes.push(Change::Replaced(target));
+        }
+
+        Ok(())
+    }
+
+    /// Directories that didn't exist before, as absolute paths inside the root
+    pub fn created_dirs(&self) -> Vec<String> {
+        self.changes.iter()
+            .filter_map(|c| match c {
+                Change::CreatedDir(dir) => dir.strip_prefix(&self.root).ok(),
+                _ => None
+            })
+            .map(|dir| format!("/{}", dir.to_string_lossy()))
+            .collect()
+    }
+
+    /// Undo every change in reverse order, restoring backed up files
+    pub fn rollback(self) {
+        for change in self.changes.into_iter().rev() {
+            match change {
+                Change::Replaced(target) => { let _ = fs::remove_file(target); },
+                Change::BackedUp { target, backup } => { let _ = fs::rename(backup, target); },
+                Change::Staged(staging) => { let _ = fs::remove_file(staging); },
+                Change::CreatedDir(dir) => { let _ = fs::remove_dir(dir); }
+            }
+        }
+    }
+
+    /// Keep the new files and drop the backups
+    pub fn commit(self) {
+        for change in self.changes {
+            if let Change::BackedUp { backup, .. } = change {
+                let _ = fs::remove_file(backup);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+/**************************************************************************/
+/* ps4cache.rs                                                            */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use ring::digest::{self, Algorithm, SHA512};
+use hex::ToHex;
+
+use crate::context::Context;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_progess_bar::root_path;
+use crate::ps4output::warning;
+
+/// A package archive in the local cache.
+pub struct CachedArchive {
+    pub name: String,
+    pub version: String,
+    pub upstream: i32,
+    pub path: PathBuf,
+    pub size: u64,
+    /// An interrupted download, resumed by the next one
+    pub partial: bool
+}
+
+/// Returns the package cache directory, /var/cache/ps4 in the root unless the [Context] names another one
+pub fn cache_dir() -> PathBuf {
+    match Context::current().cache_dir {
+        Some(dir) => dir,
+        None => root_path("/var/cache/ps4"),
+    }
+}
+
+/// Returns true if the cache is a directory the caller named, ps4 never deletes or replaces archives there
+pub fn is_user_cache_dir() -> bool {
+    Context::current().cache_dir.is_some()
+}
+
+/// Returns the short form of a checksum used in cached file names
+fn checksum_key(sha512sum: &String) -> String {
+    if sha512sum.is_empty() {
+        return "nosum".to_string();
+    }
+
+    sha512sum.chars().take(16).collect()
+}
+
+/// Returns where an archive for a repo package is cached, `<cache>/<name>/<version>-<upstream>-<checksum>.tar.gz`
+pub fn archive_path(package: &PS4Package) -> PathBuf {
+    cache_dir()
+        .join(&package.name)
+        .join(format!("{}-{}-{}.tar.gz", package.version, package.upstream, checksum_key(&package.sha512sum)))
+}
+
+/// Returns the lowercase hex sha512 of a file
+pub fn hash_file(path: &PathBuf) -> std::io::Result<String> {
+    digest_file(path, &SHA512)
+}
+
+/// Returns the lowercase hex digest of a file
+pub fn digest_file(path: &Path, algorithm: &'static Algorithm) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+
+    let mut context = digest::Context::new(algorithm);
+    let mut buffer = [0; 8192];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+    }
+
+    Ok(context.finish().as_ref().encode_hex::<String>())
+}
+
+/// Returns the cached archive for a repo package if it is present and matches the repo checksum
+pub fn find_cached(package: &PS4Package) -> Option<PathBuf> {
+    let path = archive_path(package);
+
+    if !path.is_file() {
+        return None;
+    }
+
+    if !package.sha512sum.is_empty() && hash_file(&path).ok()? != package.sha512sum {
+        if is_user_cache_dir() {
+            warning(format!("Cached archive {} doesn't match the repo checksum, skipping it.", path.display()));
+            return None;
+        }
+
+        warning(format!("Cached archive {} doesn't match the repo checksum, ignoring it.", path.display()));
+        let _ = fs::remove_file(&path);
+        return None;
+    }
+
+    Some(path)
+}
+
+/// Returns any cached archive for a package at "version-upstream"
+pub fn find_cached_version(name: &String, version: &String) -> Option<PathBuf> {
+    list_cached().into_iter()
+        .find(|a| !a.partial && &a.name == name && &format!("{}-{}", a.version, a.upstream) == version)
+        .map(|a| a.path)
+}
+
+/// Returns every archive in the cache, including interrupted downloads
+pub fn list_cached() -> Vec<CachedArchive> {
+    let mut archives: Vec<CachedArchive> = vec![];
+
+    let packages = match fs::read_dir(cache_dir()) {
+        Ok(packages) => packages,
+        Err(_) => return archives
+    };
+
+    for package_dir in packages.filter_map(|e| e.ok()) {
+        let name = package_dir.file_name().to_string_lossy().to_string();
+
+        let files = match fs::read_dir(package_dir.path()) {
+            Ok(files) => files,
+            Err(_) => continue
+        };
+
+        for file in files.filter_map(|e| e.ok()) {
+            let file_name = file.file_name().to_string_lossy().to_string();
+
+            // Downloads go to <archive>.tar.part until they are verified
+            let (stem, partial) = match (file_name.strip_suffix(".tar.gz"), file_name.strip_suffix(".tar.part")) {
+                (Some(stem), _) => (stem, false),
+                (None, Some(stem)) => (stem, true),
+                (None, None) => continue
+            };
+
+            // <version>-<upstream>-<checksum>, the version itself may contain "-"
+            let parts: Vec<&str> = stem.rsplitn(3, "-").collect();
+
+            if parts.len() != 3 {
+                continue;
+            }
+
+            let upstream: i32 = match parts[1].parse() {
+                Ok(upstream) => upstream,
+                Err(_) => continue
+            };
+
+            archives.push(CachedArchive {
+                name: name.clone(),
+                version: parts[2].to_string(),
+                upstream,
+                path: file.path(),
+                size: file.metadata().map(|m| m.len()).unwrap_or(0),
+                partial
+            });
+        }
+    }
+
+    archives
+}
@@ -0,0 +1,269 @@
+/**************************************************************************/
+/* ps4download.rs                                                         */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use indicatif::ProgressBar;
+use isahc::http::StatusCode;
+
+use crate::context::Context;
+use crate::database::ps4db::Source;
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_package_config::ps4_mirror_config_main::{get_download_retries, get_parallel_downloads, get_repo_config};
+use crate::ps4_package_progess_bar::{get, get_range, DownloadProgress};
+use crate::ps4cache::{archive_path, find_cached, hash_file, is_user_cache_dir};
+use crate::ps4mirror::load_mirrors;
+use crate::ps4output::Level;
+
+/// Failures after which a mirror is skipped for the rest of the run
+const MIRROR_FAILURE_LIMIT: u32 = 3;
+
+/// Delay before the first retry, doubled for every following one
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Failures per mirror during this run, shared by every download thread
+static MIRROR_FAILURES: OnceLock<Mutex<HashMap<String, u32>>> = OnceLock::new();
+
+fn mirror_failures() -> &'static Mutex<HashMap<String, u32>> {
+    MIRROR_FAILURES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Returns true if a mirror failed too often to be tried again
+fn mirror_skipped(mirror: &String) -> bool {
+    *mirror_failures().lock().unwrap().get(mirror).unwrap_or(&0) >= MIRROR_FAILURE_LIMIT
+}
+
+fn mirror_failed(mirror: &String, progress: &DownloadProgress) {
+    let mut failures = mirror_failures().lock().unwrap();
+    let count = failures.entry(mirror.clone()).or_insert(0);
+
+    *count += 1;
+
+    if *count == MIRROR_FAILURE_LIMIT {
+        progress.message(Level::Warning, format!("{} failed {} times, skipping it for the rest of this run.", mirror, MIRROR_FAILURE_LIMIT));
+    }
+}
+
+/// Why a single download attempt failed
+enum DownloadError {
+    Http(isahc::Error),
+    Status(StatusCode),
+    Io(io::Error)
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DownloadError::Http(e) => write!(f, "Error: {}", e),
+            DownloadError::Status(status) => write!(f, "Status: {}", status),
+            DownloadError::Io(e) => write!(f, "Error: {}", e)
+        }
+    }
+}
+
+/// Stream url into partial, continuing where a previous attempt stopped when the server supports ranges
+fn download_to(url: &String, partial: &Path, bar: &ProgressBar, progress: &DownloadProgress) -> Result<(), DownloadError> {
+    let existing = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+
+    let response = if existing > 0 { get_range(url, existing) } else { get(url) };
+    let mut response = response.map_err(DownloadError::Http)?;
+
+    let mut file = match response.status() {
+        StatusCode::PARTIAL_CONTENT => OpenOptions::new().append(true).open(partial),
+        // The server ignored the range, start over
+        StatusCode::OK => OpenOptions::new().create(true).write(true).truncate(true).open(partial),
+        // Everything was already downloaded
+        StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => return Ok(()),
+        status => return Err(DownloadError::Status(status))
+    }.map_err(DownloadError::Io)?;
+
+    let start = if response.status() == StatusCode::PARTIAL_CONTENT { existing } else { 0 };
+    let remaining = response.body().len().unwrap_or(0);
+
+    bar.set_length(start + remaining);
+    bar.set_position(start);
+    bar.reset_eta();
+    progress.add_length(remaining);
+
+    let body = response.body_mut();
+    let mut buffer = [0; 8192];
+
+    loop {
+        let read = body.read(&mut buffer).map_err(DownloadError::Io)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read]).map_err(DownloadError::Io)?;
+
+        bar.inc(read as u64);
+        progress.inc(read as u64);
+    }
+
+    Ok(())
+}
+
+/// Get an archive for a repo package, from the cache or else from the first mirror with a verified copy.
+///
+/// Downloads land in the cache, each mirror is retried with backoff. When offline only the cache is checked.
+fn fetch_package(package: &PS4Package, repo: &String, offline: bool, progress: &DownloadProgress) -> Option<(PathBuf, Source)> {
+    if let Some(cached) = find_cached(package) {
+        progress.message(Level::Info, format!("Using cached {} v{}-{}...", &package.name, &package.version, &package.upstream));
+
+        return Some((cached, Source { name: repo.clone(), url: None }));
+    }
+
+    if offline {
+        return None;
+    }
+
+    let archive = archive_path(package);
+
+    // Only a mismatched archive is left at this path, which isn't ours to replace
+    if is_user_cache_dir() && archive.exists() {
+        progress.message(Level::Error, format!("Not replacing {}, move it out of the cache directory first.", archive.display()));
+        return None;
+    }
+
+    progress.message(Level::Info, format!("Downloading {} v{}-{}...", &package.name, &package.version, &package.upstream));
+
+    if let Err(e) = fs::create_dir_all(archive.parent().unwrap()) {
+        progress.message(Level::Error, format!("Failed to create the package cache: {}", e));
+        return None;
+    }
+
+    let repo_config = match get_repo_config(repo) {
+        Some(repo_config) => repo_config,
+        None => {
+            progress.message(Level::Error, format!("Repo {} is not configured!", repo));
+            return None;
+        }
+    };
+
+    // Downloads go next to the cache entry first so an interrupted download never looks cached
+    let partial = archive.with_extension("part");
+    let bar = progress.add(&package.name);
+
+    for x in load_mirrors(&repo_config) {
+        let url = format!("{}/{}-{}-{}.tar.gz", x,
+                          &package.name, &package.version, &package.upstream);
+
+        for attempt in 0..=get_download_retries() {
+            if mirror_skipped(&x) {
+                break;
+            }
+
+            if attempt > 0 {
+                thread::sleep(RETRY_BACKOFF * 2u32.pow(attempt - 1));
+            }
+
+            let resumed = partial.exists();
+
+            if let Err(e) = download_to(&url, &partial, &bar, progress) {
+                progress.message(Level::Warning, format!("Failed to get {}. {}", &url, e));
+                mirror_failed(&x, progress);
+
+                // A missing package won't show up by asking again
+                if let DownloadError::Status(StatusCode::NOT_FOUND) = e {
+                    break;
+                }
+
+                continue;
+            }
+
+            if !package.sha512sum.is_empty() && hash_file(&partial).ok().as_ref() != Some(&package.sha512sum) {
+                let _ = fs::remove_file(&partial);
+
+                // The partial file may have been left by something else, try once more from scratch
+                if resumed {
+                    progress.message(Level::Warning, format!("Verification failed for resumed {}, downloading it again.", url));
+                    continue;
+                }
+
+                progress.message(Level::Warning, format!("Verification failed for {}, trying next mirror.", url));
+                mirror_failed(&x, progress);
+                break;
+            }
+
+            if let Err(e) = fs::rename(&partial, &archive) {
+                progress.message(Level::Error, format!("Failed to move {} into the cache: {}", &package.name, e));
+                break;
+            }
+
+            bar.finish();
+
+            return Some((archive, Source { name: repo.clone(), url: Some(url) }));
+        }
+    }
+
+    bar.abandon();
+
+    None
+}
+
+/// Fetch every package, up to `parallel_downloads` at the same time.
+///
+/// Results are in the same order as packages, None for packages no verified copy was found for.
+pub fn fetch_packages(packages: &Vec<(PS4Package, String)>, offline: bool) -> Vec<Option<(PathBuf, Source)>> {
+    let next: Mutex<usize> = Mutex::new(0);
+    let results: Mutex<Vec<Option<(PathBuf, Source)>>> = Mutex::new(vec![None; packages.len()]);
+    let progress = DownloadProgress::new(packages.len());
+
+    // The workers download into the cache of the caller's context
+    let context = Context::current();
+
+    thread::scope(|scope| {
+        for _ in 0..get_parallel_downloads().min(packages.len()) {
+            scope.spawn(|| {
+                let _context = context.enter();
+
+                loop {
+                    let index = {
+                        let mut next = next.lock().unwrap();
+                        let index = *next;
+                        *next += 1;
+                        index
+                    };
+
+                    if index >= packages.len() {
+                        break;
+                    }
+
+                    let fetched = fetch_package(&packages[index].0, &packages[index].1, offline, &progress);
+                    results.lock().unwrap()[index] = fetched;
+                }
+            });
+        }
+    });
+
+    progress.finish();
+
+    results.into_inner().unwrap()
+}
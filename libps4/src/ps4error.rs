@@ -0,0 +1,110 @@
+/**************************************************************************/
+/* ps4error.rs                                                            */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt;
+use std::io;
+
+/// Why a libps4 call failed. The CLI prints the message, embedders can match on the kind.
+#[derive(Debug)]
+pub enum Ps4Error {
+    /// The package database or a repo database couldn't be read or written
+    Db(String),
+    /// A download failed on every mirror
+    Network(String),
+    /// The config is invalid or missing something ps4 needs
+    Config(String),
+    /// Packages or files conflict, or another ps4 is working on the root
+    Conflict(String),
+    /// Dependencies can't be satisfied, or a change would break installed packages
+    Dependency(String),
+    /// Reading or writing the root failed
+    Io(io::Error),
+    /// The call changes the root and has to be made as root, libps4 never escalates by itself
+    Permission(String),
+    /// A checksum didn't match or a package is missing one
+    Verification(String),
+    /// A requested package, group or transaction doesn't exist
+    NotFound(String),
+    /// The request itself is invalid, e.g. an empty search term
+    Invalid(String),
+    /// The user answered no, nothing more to report
+    Aborted
+}
+
+impl Ps4Error {
+    /// Prefixes the message with what was being done, keeping the kind of error
+    pub fn context(self, what: impl fmt::Display) -> Ps4Error {
+        match self {
+            Ps4Error::Db(message) => Ps4Error::Db(format!("{}: {}", what, message)),
+            Ps4Error::Network(message) => Ps4Error::Network(format!("{}: {}", what, message)),
+            Ps4Error::Config(message) => Ps4Error::Config(format!("{}: {}", what, message)),
+            Ps4Error::Conflict(message) => Ps4Error::Conflict(format!("{}: {}", what, message)),
+            Ps4Error::Dependency(message) => Ps4Error::Dependency(format!("{}: {}", what, message)),
+            Ps4Error::Io(e) => Ps4Error::Io(io::Error::new(e.kind(), format!("{}: {}", what, e))),
+            Ps4Error::Permission(message) => Ps4Error::Permission(format!("{}: {}", what, message)),
+            Ps4Error::Verification(message) => Ps4Error::Verification(format!("{}: {}", what, message)),
+            Ps4Error::NotFound(message) => Ps4Error::NotFound(format!("{}: {}", what, message)),
+            Ps4Error::Invalid(message) => Ps4Error::Invalid(format!("{}: {}", what, message)),
+            Ps4Error::Aborted => Ps4Error::Aborted
+        }
+    }
+}
+
+impl fmt::Display for Ps4Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ps4Error::Db(message) => write!(f, "Database error: {}", message),
+            Ps4Error::Io(e) => write!(f, "{}", e),
+            Ps4Error::Network(message)
+            | Ps4Error::Config(message)
+            | Ps4Error::Conflict(message)
+            | Ps4Error::Dependency(message)
+            | Ps4Error::Permission(message)
+            | Ps4Error::Verification(message)
+            | Ps4Error::NotFound(message)
+            | Ps4Error::Invalid(message) => write!(f, "{}", message),
+            Ps4Error::Aborted => write!(f, "Aborted")
+        }
+    }
+}
+
+impl std::error::Error for Ps4Error {}
+
+impl From<io::Error> for Ps4Error {
+    fn from(e: io::Error) -> Ps4Error {
+        Ps4Error::Io(e)
+    }
+}
+
+impl From<rusqlite::Error> for Ps4Error {
+    fn from(e: rusqlite::Error) -> Ps4Error {
+        Ps4Error::Db(e.to_string())
+    }
+}
+
+impl From<isahc::Error> for Ps4Error {
+    fn from(e: isahc::Error) -> Ps4Error {
+        Ps4Error::Network(e.to_string())
+    }
+}
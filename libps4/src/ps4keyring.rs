@@ -0,0 +1,70 @@
+/**************************************************************************/
+/* ps4keyring.rs                                                          */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use crate::ps4_package_progess_bar::root_path;
+use crate::ps4output::warning;
+
+/// Load the public keys with the given names from the keyring in /etc/ps4.d/keys/
+///
+/// Each key is stored as `<name>.pub` containing the hex encoded ed25519 public key.
+pub fn load_trusted_keys(names: &Vec<String>) -> Vec<Vec<u8>> {
+    let mut keys: Vec<Vec<u8>> = vec![];
+
+    for name in names {
+        let path = root_path(&format!("/etc/ps4.d/keys/{}.pub", name));
+
+        let raw_key = match fs::read_to_string(&path) {
+            Ok(raw_key) => raw_key,
+            Err(_) => {
+                warning(format!("Trusted key {} not found at {}", name, path.display()));
+                continue;
+            }
+        };
+
+        match hex::decode(raw_key.trim()) {
+            Ok(key) => keys.push(key),
+            Err(_) => warning(format!("Trusted key {} is not valid hex", name)),
+        }
+    }
+
+    return keys;
+}
+
+/// Returns true if the hex encoded signature of message verifies against any of the named keys
+pub fn verify_signature(message: &[u8], signature: &str, key_names: &Vec<String>) -> bool {
+    let signature = match hex::decode(signature.trim()) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    for key in load_trusted_keys(key_names) {
+        if UnparsedPublicKey::new(&ED25519, &key).verify(message, &signature).is_ok() {
+            return true;
+        }
+    }
+
+    return false;
+}
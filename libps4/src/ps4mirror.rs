@@ -22,14 +22,19 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use std::fs::File;
-use std::io::prelude::*;
-use crate::ps4::ps4_package_config::ps4_mirror_config_main::get_config_entry;
-use crate::ps4::ps4_package_config::ps4config_init::ConfigEntries;
-use crate::ps4::ps4_package_progess_bar::get_root;
+use std::fs;
+use crate::ps4_package_config::ps4_mirror_config_main::{get_config_entry, mirrorlist_path};
+use crate::ps4_package_config::ps4config_init::{ConfigEntries, RepoConfig};
+
+/// Load the urls to try for a repo, in order.
+///
+/// A repo with a custom url only uses that url, otherwise its own mirror list or /etc/ps4.d/mirrorlist is used
+/// with `$repo`, `$arch`, `$dis_name`, `$codename` and `$version` filled in.
+pub fn load_mirrors(repo: &RepoConfig) -> Vec<String> {
+    if let Some(url) = &repo.url {
+        return vec![url.trim_end_matches('/').to_string()];
+    }
 
-/// Load mirrors for repos from mirror list
-pub fn load_mirrors() -> Vec<String> {
     let mut mirrors: Vec<String> = vec![];
 
     let arch = get_config_entry(ConfigEntries::Architecture, None, None).expect("Failed to get config architecture.");
@@ -37,18 +42,22 @@ pub fn load_mirrors() -> Vec<String> {
     let codename = get_config_entry(ConfigEntries::Codename, None, None).expect("Failed to get config codename.");
     let version = get_config_entry(ConfigEntries::Version, None, None).expect("Failed to get config version.");
 
-    let mut raw_mirrors = String::new();
-
-    File::open(get_root() + "/etc/ps4.d/mirrorlist")
-        .expect("Failed to open mirror list, is another program using it?")
-        .read_to_string(&mut raw_mirrors)
-        .expect("Failed to convert file to string");
+    let templates: Vec<String> = match &repo.mirrors {
+        Some(mirrors) => mirrors.clone(),
+        // No mirror list is fine as long as every repo has its own url or mirrors
+        None => fs::read_to_string(mirrorlist_path())
+            .unwrap_or_default()
+            .lines()
+            .map(|i| i.to_string())
+            .collect()
+    };
 
-    for i in raw_mirrors.lines() {
+    for i in templates {
         if !i.is_empty() && !i.starts_with("#") {
             mirrors.push(
-                i.to_string()
-                    .trim()
+                i.trim()
+                    .trim_end_matches('/')
+                    .replace("$repo", &repo.name)
                     .replace("$arch", arch.trim_matches(|c| c == '\\' || c == '"'))
                     .replace("$dis_name", dis_name.trim_matches(|c| c == '\\' || c == '"'))
                     .replace("$codename", codename.trim_matches(|c| c == '\\' || c == '"'))
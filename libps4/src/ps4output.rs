@@ -0,0 +1,109 @@
+/**************************************************************************/
+/* ps4output.rs                                                           */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+
+use crate::context::Context;
+use crate::ps4_package_config::ps4_mirror_config_main::get_colour;
+
+/// How important a message is, errors and warnings go to stderr, the rest to stdout.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Something failed, usually followed by ps4 giving up
+    Error,
+    /// Something is off but ps4 carries on
+    Warning,
+    /// Details of what is going on
+    Info,
+    /// A step of the transaction, e.g. installing a package
+    Action
+}
+
+impl Level {
+    fn prefix(&self) -> &'static str {
+        match self {
+            Level::Error => "error:",
+            Level::Warning => "warning:",
+            Level::Info => "  ->",
+            Level::Action => "==>"
+        }
+    }
+
+    /// ANSI colour of the prefix
+    fn colour(&self) -> &'static str {
+        match self {
+            Level::Error => "\x1b[1;31m",
+            Level::Warning => "\x1b[1;33m",
+            Level::Info => "\x1b[1;34m",
+            Level::Action => "\x1b[1;32m"
+        }
+    }
+}
+
+/// Returns true if messages should be coloured.
+///
+/// The [Context] decides if it sets `color`, otherwise `colour` in the config has to be on and stdout a terminal.
+pub fn colour_enabled() -> bool {
+    if let Some(color) = Context::current().color {
+        return color;
+    }
+
+    io::stdout().is_terminal() && get_colour()
+}
+
+/// Formats a message with the prefix of its level
+pub fn format_message(level: Level, message: &str, colour: bool) -> String {
+    if colour {
+        format!("{}{}\x1b[0m {}", level.colour(), level.prefix(), message)
+    } else {
+        format!("{} {}", level.prefix(), message)
+    }
+}
+
+/// Prints a message of any level
+pub fn message(level: Level, message: impl Display) {
+    let line = format_message(level, &message.to_string(), colour_enabled());
+
+    match level {
+        Level::Error | Level::Warning => eprintln!("{}", line),
+        Level::Info | Level::Action => println!("{}", line)
+    }
+}
+
+pub fn error(message: impl Display) {
+    self::message(Level::Error, message);
+}
+
+pub fn warning(message: impl Display) {
+    self::message(Level::Warning, message);
+}
+
+pub fn info(message: impl Display) {
+    self::message(Level::Info, message);
+}
+
+pub fn action(message: impl Display) {
+    self::message(Level::Action, message);
+}
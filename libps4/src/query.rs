@@ -0,0 +1,207 @@
+/**************************************************************************/
+/* query.rs                                                               */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Ordering;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::context::Context;
+use crate::database::ps4db::{Hold, InstalledPS4Packages};
+use crate::database::ps4dbmain::{get_all_installed, get_all_remote, get_installed_package, return_owned_files};
+use crate::packaging::ps4_packageing_setup::PS4Package;
+use crate::ps4_lock_package::TransactionGuard;
+use crate::ps4_package_config::ps4_mirror_config_main::get_enabled_repos;
+use crate::ps4_package_transactions::integrity::{check_file, FileProblem};
+use crate::ps4_package_transactions::selection::{choose_package, compare_releases, get_hold};
+use crate::ps4error::Ps4Error;
+
+/// An installed package with a newer version in the repos.
+pub struct Update {
+    pub installed: InstalledPS4Packages,
+    pub available: PS4Package,
+    /// The hold keeping the installed version, if the new version isn't allowed
+    pub held_by: Option<Hold>
+}
+
+/// What to search the synced repos for, see `search`
+#[derive(Default, Clone)]
+pub struct SearchOptions {
+    /// Treat the search term as a regular expression
+    pub regex: bool,
+
+    /// Only match package names, not descriptions
+    pub names_only: bool,
+
+    /// Only search the given repo
+    pub repo: Option<String>,
+
+    /// Only show packages in the given group
+    pub group: Option<String>,
+
+    /// Search term, matched case-insensitively
+    pub query: Vec<String>,
+}
+
+/// An installed file that no longer matches what was recorded when its package was installed.
+pub struct FileIssue {
+    pub package: String,
+    pub path: String,
+    pub problem: FileProblem
+}
+
+/// Returns every installed package
+pub fn installed(context: &Context) -> Result<Vec<InstalledPS4Packages>, Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    get_all_installed()
+}
+
+/// Returns an installed package, None if it isn't installed
+pub fn installed_package(context: &Context, name: &String) -> Result<Option<InstalledPS4Packages>, Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    get_installed_package(name)
+}
+
+/// Returns the package and repo `ps4 install` would pick for exactly this name, respecting pins and repo priorities
+pub fn candidate(context: &Context, name: &String) -> Result<Option<(PS4Package, String)>, Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    Ok(choose_package(name)?.candidates.into_iter().next())
+}
+
+/// Returns the packages in the synced repos matching a search, with the repo each one is in
+pub fn search(context: &Context, options: &SearchOptions) -> Result<Vec<(PS4Package, String)>, Ps4Error> {
+    let _context = context.enter();
+    if options.query.is_empty() && options.group.is_none() {
+        return Err(Ps4Error::Invalid("Please provide a search term. (Check ps4 --help for usage)".to_string()));
+    }
+
+    let query = options.query.join(" ");
+    let pattern = if options.regex { query.clone() } else { regex::escape(&query) };
+
+    let matcher: Regex = match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+        Ok(matcher) => matcher,
+        Err(e) => return Err(Ps4Error::Invalid(format!("Invalid search pattern: {}", e)))
+    };
+
+    let _transaction = TransactionGuard::shared()?;
+
+    let mut hits: Vec<(PS4Package, String)> = vec![];
+
+    for i in get_enabled_repos() {
+        if options.repo.is_some() && options.repo.as_ref() != Some(&i.name) {
+            continue;
+        }
+
+        for package in get_all_remote(&i.name)? {
+            if !i.allows(&package.name) {
+                continue;
+            }
+
+            if let Some(group) = &options.group {
+                if !package.groups.split(",").any(|g| g == group) {
+                    continue;
+                }
+            }
+
+            if !matcher.is_match(&package.name) && (options.names_only || !matcher.is_match(&package.description)) {
+                continue;
+            }
+
+            hits.push((package, i.name.clone()));
+        }
+    }
+
+    Ok(hits)
+}
+
+/// Returns the installed packages a repo has a newer version of, locally installed packages are skipped
+pub fn updates(context: &Context) -> Result<Vec<Update>, Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    let mut updates: Vec<Update> = vec![];
+
+    for i in get_all_installed()? {
+        let source = i.clone().source;
+        let source_name = source.split(",").collect::<Vec<&str>>()[0];
+
+        if source_name == "local" {
+            continue;
+        }
+
+        // The candidate follows pins and repo priorities, not the repo it was installed from
+        let remote_package = match choose_package(&i.name)?.candidates.into_iter().next() {
+            Some((remote_package, _)) => remote_package,
+            None => continue
+        };
+
+        // Version first, then upstream, a higher upstream of an older version is a downgrade
+        let newer = compare_releases((&remote_package.version, remote_package.upstream), (&i.version, i.upstream)) == Ordering::Greater;
+
+        if !newer {
+            continue;
+        }
+
+        let held_by = get_hold(&i.name)?.filter(|hold| !hold.allows(&remote_package.version));
+
+        updates.push(Update { installed: i, available: remote_package, held_by });
+    }
+
+    Ok(updates)
+}
+
+/// Compares the files of installed packages with what was recorded when they were installed, every package if none are given
+pub fn verify(context: &Context, packages: &Vec<String>) -> Result<Vec<FileIssue>, Ps4Error> {
+    let _context = context.enter();
+    let _transaction = TransactionGuard::shared()?;
+
+    let names: Vec<String> = if packages.is_empty() {
+        get_all_installed()?.into_iter().map(|p| p.name).collect()
+    } else {
+        for i in packages {
+            if get_installed_package(i)?.is_none() {
+                return Err(Ps4Error::NotFound(format!("Package {} is not installed!", i)));
+            }
+        }
+
+        packages.clone()
+    };
+
+    let mut issues: Vec<FileIssue> = vec![];
+
+    for name in names {
+        for file in return_owned_files(&name)? {
+            for problem in check_file(&file) {
+                issues.push(FileIssue { package: name.clone(), path: file.path.clone(), problem });
+            }
+        }
+    }
+
+    Ok(issues)
+}
@@ -24,9 +24,12 @@
 
 use std::fs::File;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 
 use data_encoding::HEXLOWER;
 use ring::digest::{Context, SHA512};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
 use rusqlite::{Connection, params};
 use serde_derive::Deserialize;
 use walkdir::{DirEntry, WalkDir};
@@ -57,17 +60,56 @@ use walkdir::{DirEntry, WalkDir};
 }
 
 /// Converts a vec of strings to a flat string separated by ","
-pub fn vec_to_string(vec: &Vec<String>) -> String {
-    let mut temp_string: String = String::new();
-    let mut x: usize = 0;
-    for i in vec {
-        temp_string.push_str(&*i);
-        if !(x == (vec.len() - 1)) {
-            temp_string.push_str(",");
+pub fn vec_to_string(vec: &[String]) -> String {
+    vec.join(",")
+}
+
+/// Returns the lowercase hex sha512 of a file
+fn hash_file(path: &Path) -> String {
+    let mut file = File::open(path).expect("Failed to open file for hashing! Aborting...");
+
+    let mut context = Context::new(&SHA512);
+    let mut buffer = [0; 1024];
+
+    loop {
+        let read = file.read(&mut buffer).expect("Failed to read file for hashing! Aborting...");
+        if read == 0 {
+            break;
         }
-        x += 1;
+        context.update(&buffer[..read]);
     }
-    temp_string
+
+    HEXLOWER.encode(context.finish().as_ref())
+}
+
+/// Find the package archive for a SRCINFO, either in the repo root or next to the SRCINFO
+fn find_package_archive(srcinfo: &Path, package_info: &PackageInfo) -> Option<PathBuf> {
+    let archive_name = format!("{}-{}-{}.tar.gz", package_info.name, package_info.version, package_info.upstream);
+    let package_dir = srcinfo.parent().unwrap_or(Path::new("."));
+
+    [
+        PathBuf::from(&archive_name),
+        package_dir.join(&archive_name),
+        package_dir.join("pkgoutput").join(&archive_name)
+    ].into_iter().find(|p| p.is_file())
+}
+
+/// Generate an ed25519 signing key (ps4.key) and its public half (ps4.pub) for the keyring
+fn generate_key() {
+    if Path::new("ps4.key").exists() {
+        println!("ps4.key already exists, refusing to overwrite it! Aborting...");
+        std::process::exit(1);
+    }
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).expect("Failed to generate key! Aborting...");
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("Failed to parse generated key! Aborting...");
+
+    File::create("ps4.key").expect("Failed to create ps4.key! Aborting...")
+        .write_all(pkcs8.as_ref()).expect("Failed to write ps4.key! Aborting...");
+    File::create("ps4.pub").expect("Failed to create ps4.pub! Aborting...")
+        .write_all(HEXLOWER.encode(key_pair.public_key().as_ref()).as_bytes()).expect("Failed to write ps4.pub! Aborting...");
+
+    println!(" Key Generated! Keep ps4.key private, ps4.pub goes in /etc/ps4.d/keys/ on clients");
 }
 
 fn is_hidden(entry: &DirEntry) -> bool {
@@ -78,6 +120,19 @@ fn is_hidden(entry: &DirEntry) -> bool {
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.iter().any(|a| a == "--genkey") {
+        generate_key();
+        return;
+    }
+
+    let key_path = args.iter()
+        .position(|a| a == "--key")
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+        .unwrap_or("ps4.key".to_string());
+
     let current_unix_time = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .expect("Time went backwards?")
@@ -96,7 +151,7 @@ fn main() {
     println!("=== Generating {} ===", &config.name);
     */
 
-    println!(" Generating {} at {} ", "PS4 Database", current_unix_time);
+    println!(" Generating PS4 Database at {} ", current_unix_time);
 
     // Create the initial sqlite database with rust-sqlite
     println!(" Creating Database ");
@@ -140,6 +195,14 @@ fn main() {
 
             println!(" Inserting: {} v{}-{}", &package_info.name, &package_info.version, &package_info.upstream);
 
+            let checksum = match find_package_archive(entry.path(), &package_info) {
+                Some(archive) => hash_file(&archive),
+                None => {
+                    eprintln!("warning: No archive found for {} v{}-{}, leaving checksum empty", &package_info.name, &package_info.version, &package_info.upstream);
+                    String::new()
+                }
+            };
+
             tx.execute("
                 INSERT OR REPLACE INTO packages
                     (
@@ -171,7 +234,7 @@ fn main() {
                 vec_to_string(&package_info.provides),
                 vec_to_string(&package_info.conflicts),
                 vec_to_string(&package_info.replaces),
-                ""
+                &checksum
             ]
             ).expect("Failed to insert package into database! Aborting...");
 
@@ -187,21 +250,7 @@ fn main() {
     println!(" Database Populated!");
 
     println!(" Generating Hash");
-    let mut database_file = File::open("ps4.db").expect("Failed to open ps4.db! Aborting...");
-
-    let mut context = Context::new(&SHA512);
-    let mut buffer = [0; 1024];
-
-    loop {
-        let read = database_file.read(&mut buffer).expect("Failed to read ps4.db! Aborting...");
-        if read == 0 {
-            break;
-        }
-        context.update(&buffer[..read]);
-    }
-
-    let hash = context.finish();
-    let hash_string = HEXLOWER.encode(hash.as_ref()).to_lowercase();
+    let hash_string = hash_file(Path::new("ps4.db"));
 
     println!("=> Hash: {}", &hash_string);
 
@@ -210,6 +259,20 @@ fn main() {
     println!(" Hash File Created!");
     println!(" Hash Generated! ");
 
+    if Path::new(&key_path).exists() {
+        println!(" Signing Hash");
+        let key_bytes = std::fs::read(&key_path).expect("Failed to read signing key! Aborting...");
+        let key_pair = Ed25519KeyPair::from_pkcs8(&key_bytes).expect("Signing key is not a valid ed25519 key! Aborting...");
+        let signature = key_pair.sign(hash_string.as_bytes());
+
+        File::create("ps4.sig").expect("Failed to create ps4.sig! Aborting...")
+            .write_all(HEXLOWER.encode(signature.as_ref()).as_bytes()).expect("Failed to write to ps4.sig! Aborting...");
+        println!(" Signature Created!");
+    } else {
+        eprintln!("warning: No signing key found at {}, the repo will be unsigned", &key_path);
+        let _ = std::fs::remove_file("ps4.sig");
+    }
+
     println!(" Finished!")
 }
 
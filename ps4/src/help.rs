@@ -1,5 +1,5 @@
 /**************************************************************************/
-/* mod.rs                                                                 */
+/* help.rs                                                                */
 /**************************************************************************/
 /*                         This file is part of:                          */
 /*                           PS4 PACKGE MANAGER                           */
@@ -22,12 +22,22 @@
  *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-pub mod packaging;
-pub mod ps4_lock_package;
-pub mod database;
-/// Utility functions and structs relating to ps4's configuration.
-pub mod ps4_package_config;
-pub mod ps4mirror;
-pub mod ps4_package_progess_bar;
-pub mod ps4_package_manager_commands;
-pub mod ps4_package_transactions;
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+use clap_mangen::Man;
+
+use crate::ps4_cli::Cli;
+
+/// Print completions for the given shell, generated from the same definitions as the parser
+pub fn completions(shell: Shell) {
+    generate(shell, &mut Cli::command(), "ps4", &mut io::stdout());
+}
+
+/// Print the ps4 man page in roff, generated from the same definitions as the parser
+pub fn manpage() {
+    Man::new(Cli::command())
+        .render(&mut io::stdout())
+        .expect("Failed to write man page");
+}
@@ -25,61 +25,71 @@
 #![allow(clippy::all)]
 
 
-use std::env;
-
-
-mod ps4;
-
-/// Get a static string of the current ps4 version
-pub fn get_version() -> &'static str {
-    env!("CARGO_PKG_VERSION")
+use libps4::Ps4Error;
+use libps4::ps4_lock_package;
+use libps4::ps4output::error;
+use libps4::ps4_package_manager_commands::*;
+
+use ps4_cli::{Cli, Commands, HistoryCommand};
+
+mod help;
+mod ps4_cli;
+
+/// Re-runs ps4 as root, keeping INSTALL_ROOT and the PS4_ variables.
+///
+/// libps4 checks for root before touching anything, so the command simply starts over. Only returns if it can't escalate.
+fn escalate() {
+    if let Err(e) = sudo::with_env(&["INSTALL_ROOT", "PS4_"]) {
+        error(format!("Failed to escalate to root: {}", e));
+    }
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    // Check if any command was supplied
-    if args.len() < 2 {
-        ps4::ps4_package_manager_commands::help::help();
-        std::process::exit(0);
-    }
-
-    let command: String = args[1].to_lowercase();
-
-    match &command[..] {
-        // Help commands    
-        "-h" => ps4::ps4_package_manager_commands::help::help(),
-        "--help" => ps4::ps4_package_manager_commands::help::help(),
-
-        // Sync commands
-        "s" => ps4::ps4_package_manager_commands::sync::sync(),
-        "--sync" => ps4::ps4_package_manager_commands::sync::sync(),
-
-        // Upgrade commands
-        "u" => ps4::ps4_package_manager_commands::upgrade::upgrade(),
-        "--upgrade" => ps4::ps4_package_manager_commands::upgrade::upgrade(),
+    let cli = Cli::parse_args();
 
+    let context = match cli.global.context() {
+        Ok(context) => context,
+        Err(e) => {
+            error(e);
+            std::process::exit(1);
+        }
+    };
+
+    // Messages printed here follow the context too, and an interrupted command releases the lock on the way out
+    let _context = context.enter();
+    ps4_lock_package::handle_signals();
+
+    let result = match cli.command {
+        Commands::Sync => sync::sync(&context),
+        Commands::Upgrade => upgrade::upgrade(&context),
+        Commands::Install { options, packages } => install::install(&options.context(&context), packages, options.into()),
+        Commands::Groupinstall { groups } => groupinstall::group_install(&context, groups),
+        Commands::Local { ignore_holds, paths } => localinstall::local_install(&context, paths, ignore_holds),
+        Commands::Remove { packages } => remove::remove(&context, packages),
+        Commands::Info { packages } => info::info(&context, packages),
+        Commands::Search { options } => search::search(&context, options.into()),
+        Commands::History { command } => history::history(&context, command.map(|HistoryCommand::Show { id }| id)),
+        Commands::Undo { ignore_holds, id } => history::undo(&context, id, ignore_holds),
+        Commands::Clean { options } => clean::clean(&context, options.into()),
+        Commands::Hold { package, version } => hold::hold(&context, package, version),
+        Commands::Unhold { packages } => hold::unhold(&context, packages),
+        Commands::Bootstrap { dir, groups } => bootstrap::bootstrap(&context, dir, groups),
+        Commands::Verify { packages } => verify::verify(&context, packages),
+        Commands::List => list::list(&context),
+        Commands::Completions { shell } => Ok(help::completions(shell)),
+        Commands::Manpage => Ok(help::manpage()),
+    };
+
+    // Every lock and temp directory is released by now, the commands' guards are gone
+    if let Err(Ps4Error::Permission(_)) = &result {
+        escalate();
+    }
 
-        // Install commands
-        "i" => ps4::ps4_package_manager_commands::install::install(args),
-        "--install" => ps4::ps4_package_manager_commands::install::install(args),
-        "-U" => ps4::ps4_package_manager_commands::localinstall::local_install(args),
-        "--local" => ps4::ps4_package_manager_commands::localinstall::local_install(args),
-        "gi" => ps4::ps4_package_manager_commands::groupinstall::group_install(args),
-        "--groupinstall" => ps4::ps4_package_manager_commands::groupinstall::group_install(args),
-
-        // Remove commands
-        "remove" => ps4::ps4_package_manager_commands::remove::remove(args),
-        "--uninstall" => ps4::ps4_package_manager_commands::remove::remove(args),
-
-        // Info commands
-
-        // List commands
-        "--list" => ps4::ps4_package_manager_commands::list::list(),
-
-        // Specify that command is invalid and show help command
-        _ => {
-            println!("ps4: Invalid command \"{}\", use {{-h --help}} for valid commands.", command);
+    if let Err(e) = result {
+        if !matches!(e, Ps4Error::Aborted) {
+            error(e);
         }
+
+        std::process::exit(1);
     }
 }
-
@@ -0,0 +1,410 @@
+/**************************************************************************/
+/* ps4_cli.rs                                                             */
+/**************************************************************************/
+/*                         This file is part of:                          */
+/*                           PS4 PACKGE MANAGER                           */
+/*                        https://github.com/TigerClips1                  */
+/**************************************************************************/
+/*
+ *  Copyright (c) 2024 TigerClips1 <tigerclips1@ps4repo.site>
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+use libps4::context::Context;
+use libps4::Ps4Error;
+use libps4::ps4_package_manager_commands::clean::CleanOptions;
+use libps4::ps4_package_manager_commands::install::InstallOptions;
+use libps4::query::SearchOptions;
+
+/// The JaguarLinux package manager
+#[derive(Parser)]
+#[command(name = "ps4", version = libps4::get_version(), arg_required_else_help = true)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalOptions,
+
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+impl Cli {
+    /// Parse the command line, accepting the old `-gi` spelling of `--groupinstall`.
+    ///
+    /// clap has no multi character short flags, so it is rewritten before parsing and stays out of the help.
+    pub fn parse_args() -> Cli {
+        let mut options_done = false;
+
+        Cli::parse_from(env::args_os().map(|arg| {
+            if arg == "--" {
+                options_done = true;
+            }
+
+            if !options_done && arg == "-gi" {
+                return OsString::from("--groupinstall");
+            }
+
+            arg
+        }))
+    }
+}
+
+/// When to colour output.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never
+}
+
+/// Options accepted by every command.
+#[derive(Args)]
+#[command(next_help_heading = "Global Options")]
+pub struct GlobalOptions {
+    /// Operate on the system rooted at DIR instead of / (same as INSTALL_ROOT)
+    #[arg(long, visible_alias = "sysroot", global = true, value_name = "DIR")]
+    pub root: Option<String>,
+
+    /// Read the configuration from FILE (same as PS4_CONFIG)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<String>,
+
+    /// Answer prompts without asking (same as PS4_NOCONFIRM=1)
+    #[arg(long, visible_alias = "yes", global = true)]
+    pub noconfirm: bool,
+
+    /// When to colour output
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Print more about what ps4 is doing
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Answer downgrade prompts (--noconfirm default: deny)
+    #[arg(long, global = true, value_parser = ["allow", "deny"])]
+    pub downgrade: Option<String>,
+
+    /// Answer file conflict prompts (--noconfirm default: abort)
+    #[arg(long, global = true, value_parser = ["overwrite", "abort"])]
+    pub file_conflicts: Option<String>,
+
+    /// Answer prompts to replace an installed provider (--noconfirm default: accept)
+    #[arg(long, global = true, value_parser = ["accept", "reject"])]
+    pub replace: Option<String>,
+
+    /// Answer prompts to break a lock its owner didn't release (--noconfirm default: keep)
+    #[arg(long, global = true, value_parser = ["break", "keep"])]
+    pub stale_lock: Option<String>,
+}
+
+/// Returns the answer a prompt option gives, from the option or else its env variable
+fn policy(option: &Option<String>, var: &str, yes: &str, no: &str) -> Result<Option<bool>, Ps4Error> {
+    let Some(value) = option.clone().or(env::var(var).ok()) else {
+        return Ok(None);
+    };
+
+    if value == yes {
+        Ok(Some(true))
+    } else if value == no {
+        Ok(Some(false))
+    } else {
+        Err(Ps4Error::Invalid(format!("Invalid value {:?} for {}, expected {} or {}.", value, var, yes, no)))
+    }
+}
+
+/// Returns true if a switch is set in the environment
+fn env_flag(var: &str) -> bool {
+    env::var(var).map(|val| val == "1" || val.to_lowercase() == "true" || val.to_lowercase() == "yes").unwrap_or(false)
+}
+
+impl GlobalOptions {
+    /// Where libps4 should work and how it talks to the user, from the options or else the env variables
+    /// of the same name, INSTALL_ROOT, PS4_CONFIG, PS4_CACHE_DIR, PS4_NOCONFIRM and so on
+    pub fn context(&self) -> Result<Context, Ps4Error> {
+        let root = self.root.clone().or(env::var("INSTALL_ROOT").ok()).filter(|root| !root.is_empty());
+        let config = self.config.clone().or(env::var("PS4_CONFIG").ok());
+
+        let color = match self.color {
+            ColorChoice::Always => Some(true),
+            ColorChoice::Never => Some(false),
+            ColorChoice::Auto => match env::var("PS4_COLOR").as_deref() {
+                Ok("always") => Some(true),
+                Ok("never") => Some(false),
+                _ if env::var("NO_COLOR").map(|val| !val.is_empty()).unwrap_or(false) => Some(false),
+                _ => None
+            }
+        };
+
+        Ok(Context {
+            // Keep the root absolute, every path is built by appending to it
+            root: root.map(|root| env::current_dir().map(|cwd| cwd.join(&root)).unwrap_or(PathBuf::from(root))),
+            config: config.map(PathBuf::from),
+            cache_dir: env::var_os("PS4_CACHE_DIR").map(PathBuf::from),
+            noconfirm: self.noconfirm || env_flag("PS4_NOCONFIRM"),
+            color,
+            verbose: self.verbose || env::var("PS4_VERBOSE").map(|val| val == "1").unwrap_or(false),
+            downgrade: policy(&self.downgrade, "PS4_DOWNGRADE", "allow", "deny")?,
+            file_conflicts: policy(&self.file_conflicts, "PS4_FILE_CONFLICTS", "overwrite", "abort")?,
+            replace: policy(&self.replace, "PS4_REPLACE", "accept", "reject")?,
+            stale_lock: policy(&self.stale_lock, "PS4_STALE_LOCK", "break", "keep")?,
+        })
+    }
+}
+
+/// Options for `ps4 install`
+#[derive(Args)]
+pub struct InstallArgs {
+    /// Install packages whose checksum can't be verified
+    #[arg(long)]
+    pub allow_unverified: bool,
+
+    /// Only download the packages (and their dependencies) into the cache
+    #[arg(long, conflicts_with = "offline")]
+    pub download_only: bool,
+
+    /// Install only from already cached archives, never touch the network
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Change held packages anyway
+    #[arg(long)]
+    pub ignore_holds: bool,
+
+    /// Use DIR as the package cache instead of /var/cache/ps4
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<String>,
+}
+
+impl InstallArgs {
+    /// The global context, using --cache-dir if given
+    pub fn context(&self, global: &Context) -> Context {
+        Context {
+            cache_dir: self.cache_dir.clone().map(PathBuf::from).or(global.cache_dir.clone()),
+            ..global.clone()
+        }
+    }
+}
+
+impl From<InstallArgs> for InstallOptions {
+    fn from(args: InstallArgs) -> InstallOptions {
+        InstallOptions {
+            allow_unverified: args.allow_unverified,
+            download_only: args.download_only,
+            offline: args.offline,
+            ignore_holds: args.ignore_holds,
+        }
+    }
+}
+
+/// Options for `ps4 search`
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Treat the search term as a regular expression
+    #[arg(long)]
+    pub regex: bool,
+
+    /// Only match package names, not descriptions
+    #[arg(long)]
+    pub names_only: bool,
+
+    /// Only search the given repo
+    #[arg(long, value_name = "REPO")]
+    pub repo: Option<String>,
+
+    /// Only show packages in the given group
+    #[arg(long, value_name = "GROUP")]
+    pub group: Option<String>,
+
+    /// Search term, matched case-insensitively
+    pub query: Vec<String>,
+}
+
+impl From<SearchArgs> for SearchOptions {
+    fn from(args: SearchArgs) -> SearchOptions {
+        SearchOptions {
+            regex: args.regex,
+            names_only: args.names_only,
+            repo: args.repo,
+            group: args.group,
+            query: args.query,
+        }
+    }
+}
+
+/// Options for `ps4 clean`
+#[derive(Args)]
+pub struct CleanArgs {
+    /// Keep the newest N versions of each package
+    #[arg(long, value_name = "N")]
+    pub keep: Option<usize>,
+
+    /// Remove every archive of packages that aren't installed
+    #[arg(long)]
+    pub uninstalled: bool,
+
+    /// Remove every cached archive, interrupted downloads included
+    #[arg(long)]
+    pub all: bool,
+}
+
+impl From<CleanArgs> for CleanOptions {
+    fn from(args: CleanArgs) -> CleanOptions {
+        CleanOptions {
+            keep: args.keep,
+            uninstalled: args.uninstalled,
+            all: args.all,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Synchronize package databases with remotes
+    #[command(short_flag = 's', long_flag = "sync")]
+    Sync,
+
+    /// Check for (and then install) package updates
+    #[command(short_flag = 'u', long_flag = "upgrade")]
+    Upgrade,
+
+    /// Install packages and their dependencies from the synced repos
+    #[command(short_flag = 'i', long_flag = "install")]
+    Install {
+        #[command(flatten)]
+        options: InstallArgs,
+
+        /// Packages to install, optionally with a version constraint such as glibc>=2.38
+        #[arg(required = true)]
+        packages: Vec<String>,
+    },
+
+    /// Install every package in one or more groups
+    #[command(long_flag = "groupinstall", visible_alias = "gi")]
+    Groupinstall {
+        #[arg(required = true)]
+        groups: Vec<String>,
+    },
+
+    /// Install packages from local archives
+    #[command(short_flag = 'U', long_flag = "local", visible_alias = "localinstall")]
+    Local {
+        /// Change held packages anyway
+        #[arg(long)]
+        ignore_holds: bool,
+
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+
+    /// Uninstall packages
+    #[command(short_flag = 'r', long_flag = "uninstall", visible_alias = "uninstall")]
+    Remove {
+        #[arg(required = true)]
+        packages: Vec<String>,
+    },
+
+    /// Show information about packages, installed or not, or local archives
+    #[command(long_flag = "info")]
+    Info {
+        #[arg(required = true)]
+        packages: Vec<String>,
+    },
+
+    /// Search package names and descriptions in all synced repos
+    #[command(long_flag = "search")]
+    Search {
+        #[command(flatten)]
+        options: SearchArgs,
+    },
+
+    /// List past transactions
+    History {
+        #[command(subcommand)]
+        command: Option<HistoryCommand>,
+    },
+
+    /// Reverse a transaction using cached package archives
+    Undo {
+        /// Change held packages anyway
+        #[arg(long)]
+        ignore_holds: bool,
+
+        id: i64,
+    },
+
+    /// Remove cached package archives, keeps the last 3 versions of each package by default
+    #[command(long_flag = "clean")]
+    Clean {
+        #[command(flatten)]
+        options: CleanArgs,
+    },
+
+    /// Hold a package so upgrade and install leave it alone, lists holds without a package
+    Hold {
+        package: Option<String>,
+
+        /// Only allow versions matching this glob, e.g. "13.*"
+        version: Option<String>,
+    },
+
+    /// Remove the hold on packages
+    Unhold {
+        #[arg(required = true)]
+        packages: Vec<String>,
+    },
+
+    /// Create a new root filesystem in DIR and install groups into it
+    Bootstrap {
+        /// Directory to create the new root in
+        dir: String,
+
+        #[arg(required = true)]
+        groups: Vec<String>,
+    },
+
+    /// Check installed files for ones that are missing, modified or have different permissions
+    Verify {
+        /// Packages to check, every installed package if none are given
+        packages: Vec<String>,
+    },
+
+    /// List all installed packages with their version and source
+    #[command(long_flag = "list")]
+    List,
+
+    /// Print shell completions for ps4
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Print the ps4 man page
+    Manpage,
+}
+
+#[derive(Subcommand)]
+pub enum HistoryCommand {
+    /// Show what a single transaction changed
+    Show {
+        id: i64,
+    },
+}